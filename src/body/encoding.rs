@@ -0,0 +1,204 @@
+//! `Content-Encoding` (RFC 9110 section 8.4) strategies.
+//!
+//! available content codings depend on the `gzip`, `deflate` and `br` features, which pull in
+//! pure Rust codecs.
+
+use std::{fmt::Debug, io::Write, ops::RangeInclusive};
+
+use proptest::{
+  collection::vec,
+  prelude::{Strategy, any},
+  prop_oneof,
+  sample::select,
+};
+
+use crate::{body::octets, header::HeaderField};
+
+/// `Content-Encoding` header field name.
+pub const CONTENT_ENCODING: &str = "content-encoding";
+
+/// content coding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentCoding {
+  /// gzip file format (RFC 1952).
+  #[cfg(feature = "gzip")]
+  Gzip,
+  /// zlib data format (RFC 1950) containing a deflate compressed stream.
+  #[cfg(feature = "deflate")]
+  Deflate,
+  /// brotli compressed data format (RFC 7932).
+  #[cfg(feature = "br")]
+  Brotli,
+}
+
+/// content codings enabled by features.
+const CODINGS: &[ContentCoding] = &[
+  #[cfg(feature = "gzip")]
+  ContentCoding::Gzip,
+  #[cfg(feature = "deflate")]
+  ContentCoding::Deflate,
+  #[cfg(feature = "br")]
+  ContentCoding::Brotli,
+];
+
+impl ContentCoding {
+  /// lower cased content coding name.
+  pub fn name(self) -> &'static str {
+    match self {
+      #[cfg(feature = "gzip")]
+      Self::Gzip => "gzip",
+      #[cfg(feature = "deflate")]
+      Self::Deflate => "deflate",
+      #[cfg(feature = "br")]
+      Self::Brotli => "br",
+    }
+  }
+
+  /// encode octets.
+  ///
+  /// # Arguments
+  /// * `input`: octets to encode.
+  /// * `level`: compression level, from `0` to `9`.
+  pub fn encode(self, input: &[u8], level: u32) -> Vec<u8> {
+    // writing to a vector never fails.
+    match self {
+      #[cfg(feature = "gzip")]
+      Self::Gzip => {
+        let mut encoder =
+          flate2::write::GzEncoder::new(vec![], flate2::Compression::new(level.min(9)));
+        encoder.write_all(input).expect("gzip encoding");
+        encoder.finish().expect("gzip encoding")
+      }
+      #[cfg(feature = "deflate")]
+      Self::Deflate => {
+        let mut encoder =
+          flate2::write::ZlibEncoder::new(vec![], flate2::Compression::new(level.min(9)));
+        encoder.write_all(input).expect("deflate encoding");
+        encoder.finish().expect("deflate encoding")
+      }
+      #[cfg(feature = "br")]
+      Self::Brotli => {
+        let mut encoder = brotli::CompressorWriter::new(vec![], 4096, level.min(9), 22);
+        encoder.write_all(input).expect("brotli encoding");
+        encoder.into_inner()
+      }
+    }
+  }
+}
+
+/// body with content codings applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encoded<M> {
+  /// model of the payload.
+  pub model: M,
+  /// payload, before applying the content codings.
+  pub payload: Vec<u8>,
+  /// content codings, in the order they are applied.
+  pub codings: Vec<ContentCoding>,
+}
+
+/// strategy for applying content codings to a body.
+///
+/// codings are listed in `Content-Encoding` in the order they are applied, `gzip, deflate`
+/// meaning the payload is gzipped then deflated. Compression levels are random.
+///
+/// # Arguments
+/// * `body`: strategy generating a body model, the header fields describing the body and the
+///   body, like [`structured_body`](crate::body::structured_body).
+/// * `coding_count_range`: range of the number of content codings, stacked when more than one.
+///
+/// # Returns
+/// [`Encoded`], the header fields describing the body followed by `Content-Encoding`, and the
+/// encoded body.
+pub fn content_encoded<M: Debug + Clone>(
+  body: impl Strategy<Value = (M, Vec<HeaderField>, Vec<u8>)>,
+  coding_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Encoded<M>, Vec<HeaderField>, Vec<u8>)> {
+  (body, vec((select(CODINGS), 0..=9u32), coding_count_range)).prop_map(
+    |((model, mut headers, payload), codings)| {
+      let mut encoded = payload.clone();
+      for (coding, level) in &codings {
+        encoded = coding.encode(&encoded, *level);
+      }
+      let codings = codings.into_iter().map(|(coding, _)| coding).collect::<Vec<_>>();
+      headers.push(HeaderField {
+        name: CONTENT_ENCODING.to_string(),
+        value: codings.iter().map(|coding| coding.name()).collect::<Vec<_>>().join(", "),
+      });
+      (Encoded { model, payload, codings }, headers, encoded)
+    },
+  )
+}
+
+/// strategy for generating octets which are random or highly compressible, like long runs of
+/// the same octet, for testing decompression limits.
+///
+/// # Arguments
+/// * `max_len`: maximum length of random octets.
+/// * `max_run_len`: maximum length of a run of the same octet.
+///
+/// # Returns
+/// the octets, the header fields describing the body, `Content-Type`, and the octets.
+pub fn compressible_octets(
+  max_len: usize,
+  max_run_len: usize,
+) -> impl Strategy<Value = (Vec<u8>, Vec<HeaderField>, Vec<u8>)> {
+  prop_oneof![
+    octets(max_len),
+    (octets(max_len), vec((any::<u8>(), 0..=max_run_len), 1..=4)).prop_map(
+      |((_, headers, mut body), runs)| {
+        for (octet, len) in runs {
+          body.extend(std::iter::repeat_n(octet, len));
+        }
+        (body.clone(), headers, body)
+      }
+    ),
+  ]
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Read;
+
+  use proptest::proptest;
+
+  use super::*;
+  use crate::body::structured_body;
+
+  fn decode(coding: &str, input: &[u8]) -> Vec<u8> {
+    let mut output = vec![];
+    match coding {
+      #[cfg(feature = "gzip")]
+      "gzip" => flate2::read::GzDecoder::new(input).read_to_end(&mut output),
+      #[cfg(feature = "deflate")]
+      "deflate" => flate2::read::ZlibDecoder::new(input).read_to_end(&mut output),
+      #[cfg(feature = "br")]
+      "br" => brotli::Decompressor::new(input, 4096).read_to_end(&mut output),
+      coding => panic!("unexpected content coding {coding:?}"),
+    }
+    .unwrap();
+    output
+  }
+
+  proptest! {
+    #[test]
+    fn content_encoded_works(
+      (encoded, headers, body) in content_encoded(structured_body(64), 1..=3)
+    ) {
+      let field = headers.iter().find(|field| field.name == CONTENT_ENCODING).unwrap();
+      let codings = field.value.split(", ").collect::<Vec<_>>();
+      assert_eq!(codings, encoded.codings.iter().map(|coding| coding.name()).collect::<Vec<_>>());
+      // codings are removed in the reverse order.
+      let decoded = codings.iter().rev().fold(body, |body, coding| decode(coding, &body));
+      assert_eq!(decoded, encoded.payload);
+    }
+
+    #[test]
+    fn compressible_octets_works(
+      (encoded, _, body) in content_encoded(compressible_octets(64, 1 << 16), 1..=1)
+    ) {
+      assert_eq!(encoded.model, encoded.payload);
+      assert_eq!(decode(encoded.codings[0].name(), &body), encoded.payload);
+    }
+  }
+}
@@ -0,0 +1,85 @@
+//! `application/x-www-form-urlencoded` body strategies.
+
+use std::ops::RangeInclusive;
+
+use proptest::{collection::vec, prelude::Strategy};
+
+use crate::{
+  header::{HeaderField, common::content_type::CONTENT_TYPE},
+  request_line::target::components::query::form_urlencoded_octets,
+};
+
+/// form field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+  /// decoded name.
+  pub name: Vec<u8>,
+  /// decoded value.
+  pub value: Vec<u8>,
+}
+
+/// strategy for generating `application/x-www-form-urlencoded` body.
+///
+/// names and values use the query characters, spaces being encoded as `+`, and every other
+/// octet being percent encoded.
+///
+/// # Arguments
+/// * `field_count_range`: range of the number of fields.
+///
+/// # Returns
+/// form fields, the header fields describing the body, `Content-Type`, and the body.
+pub fn form_urlencoded(
+  field_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Vec<FormField>, Vec<HeaderField>, Vec<u8>)> {
+  vec((form_urlencoded_octets(0, 32), form_urlencoded_octets(0, 32)), field_count_range).prop_map(
+    |fields| {
+      let mut body = vec![];
+      let fields = fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, ((name, name_repr), (value, value_repr)))| {
+          if i > 0 {
+            body.push(b'&');
+          }
+          body.extend_from_slice(&name_repr);
+          body.push(b'=');
+          body.extend_from_slice(&value_repr);
+          FormField { name, value }
+        })
+        .collect();
+      let headers = vec![HeaderField {
+        name: CONTENT_TYPE.to_string(),
+        value: "application/x-www-form-urlencoded".to_string(),
+      }];
+      (fields, headers, body)
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+  use url::form_urlencoded;
+
+  use super::*;
+
+  proptest! {
+    #[test]
+    fn form_urlencoded_works((fields, _, body) in form_urlencoded(0..=8)) {
+      let parsed = form_urlencoded::parse(&body)
+        .map(|(name, value)| FormField {
+          name: name.into_owned().into_bytes(),
+          value: value.into_owned().into_bytes(),
+        })
+        .collect::<Vec<_>>();
+      let expected = fields
+        .iter()
+        .map(|field| FormField {
+          name: String::from_utf8_lossy(&field.name).into_owned().into_bytes(),
+          value: String::from_utf8_lossy(&field.value).into_owned().into_bytes(),
+        })
+        .collect::<Vec<_>>();
+      assert_eq!(parsed, expected);
+    }
+  }
+}
@@ -0,0 +1,234 @@
+//! `application/json` body (RFC 8259) strategies.
+
+use std::fmt::Write;
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+};
+
+use crate::header::{HeaderField, common::content_type::CONTENT_TYPE};
+
+/// JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+  Null,
+  Bool(bool),
+  /// finite number.
+  Number(f64),
+  String(String),
+  Array(Vec<JsonValue>),
+  /// members in order, with unique names.
+  Object(Vec<(String, JsonValue)>),
+}
+
+/// serialization style of a JSON document.
+#[derive(Debug, Clone, Copy)]
+struct JsonStyle {
+  /// line breaks and indentation between array elements and object members.
+  pretty: bool,
+  /// non ASCII characters escaped as `\uXXXX`, with surrogate pairs.
+  ascii_only: bool,
+  /// `/` escaped as `\/`.
+  escape_solidus: bool,
+}
+
+fn json_style() -> impl Strategy<Value = JsonStyle> {
+  (any::<bool>(), any::<bool>(), any::<bool>()).prop_map(|(pretty, ascii_only, escape_solidus)| {
+    JsonStyle { pretty, ascii_only, escape_solidus }
+  })
+}
+
+impl JsonValue {
+  fn write_string(value: &str, style: JsonStyle, output: &mut String) {
+    output.push('"');
+    for c in value.chars() {
+      match c {
+        '"' => output.push_str("\\\""),
+        '\\' => output.push_str("\\\\"),
+        '/' if style.escape_solidus => output.push_str("\\/"),
+        '\u{8}' => output.push_str("\\b"),
+        '\u{c}' => output.push_str("\\f"),
+        '\n' => output.push_str("\\n"),
+        '\r' => output.push_str("\\r"),
+        '\t' => output.push_str("\\t"),
+        '\0'..='\u{1f}' => {
+          let _ = write!(output, "\\u{:04x}", u32::from(c));
+        }
+        _ if style.ascii_only && !c.is_ascii() => {
+          for unit in c.encode_utf16(&mut [0; 2]) {
+            let _ = write!(output, "\\u{unit:04X}");
+          }
+        }
+        _ => output.push(c),
+      }
+    }
+    output.push('"');
+  }
+
+  fn write(&self, style: JsonStyle, depth: usize, output: &mut String) {
+    let line_break = |output: &mut String, depth: usize| {
+      if style.pretty {
+        output.push('\n');
+        output.push_str(&"  ".repeat(depth));
+      }
+    };
+    match self {
+      Self::Null => output.push_str("null"),
+      Self::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+      Self::Number(value) => {
+        let _ = write!(output, "{value}");
+      }
+      Self::String(value) => Self::write_string(value, style, output),
+      Self::Array(values) => {
+        output.push('[');
+        for (i, value) in values.iter().enumerate() {
+          if i > 0 {
+            output.push(',');
+          }
+          line_break(output, depth + 1);
+          value.write(style, depth + 1, output);
+        }
+        if !values.is_empty() {
+          line_break(output, depth);
+        }
+        output.push(']');
+      }
+      Self::Object(members) => {
+        output.push('{');
+        for (i, (name, value)) in members.iter().enumerate() {
+          if i > 0 {
+            output.push(',');
+          }
+          line_break(output, depth + 1);
+          Self::write_string(name, style, output);
+          output.push_str(if style.pretty { ": " } else { ":" });
+          value.write(style, depth + 1, output);
+        }
+        if !members.is_empty() {
+          line_break(output, depth);
+        }
+        output.push('}');
+      }
+    }
+  }
+}
+
+/// compact serialization, non ASCII characters unescaped.
+impl std::fmt::Display for JsonValue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut output = String::new();
+    self.write(
+      JsonStyle { pretty: false, ascii_only: false, escape_solidus: false },
+      0,
+      &mut output,
+    );
+    f.write_str(&output)
+  }
+}
+
+fn json_string() -> impl Strategy<Value = String> {
+  prop_oneof![
+    3 => "[a-zA-Z0-9 _\\-]{0,16}",
+    1 => "\\PC{0,16}",
+    1 => any::<String>(),
+  ]
+}
+
+fn json_number() -> impl Strategy<Value = f64> {
+  prop_oneof![
+    any::<i32>().prop_map(f64::from),
+    proptest::num::f64::NORMAL | proptest::num::f64::SUBNORMAL | proptest::num::f64::ZERO,
+  ]
+}
+
+/// strategy for generating JSON value.
+///
+/// # Arguments
+/// * `max_depth`: maximum nesting depth of arrays and objects.
+/// * `max_items`: maximum number of array elements or object members.
+pub fn json_value(max_depth: u32, max_items: usize) -> impl Strategy<Value = JsonValue> {
+  let leaf = prop_oneof![
+    Just(JsonValue::Null),
+    any::<bool>().prop_map(JsonValue::Bool),
+    json_number().prop_map(JsonValue::Number),
+    json_string().prop_map(JsonValue::String),
+  ];
+  leaf.prop_recursive(max_depth, 64, max_items as u32, move |inner| {
+    prop_oneof![
+      vec(inner.clone(), 0..=max_items).prop_map(JsonValue::Array),
+      vec((json_string(), inner), 0..=max_items).prop_map(|mut members| {
+        let mut names = std::collections::HashSet::new();
+        members.retain(|(name, _)| names.insert(name.clone()));
+        JsonValue::Object(members)
+      }),
+    ]
+  })
+}
+
+/// strategy for generating `application/json` body.
+///
+/// documents are compact or pretty printed, with optional escaping of non ASCII characters and
+/// `/`.
+///
+/// # Arguments
+/// * `max_depth`: maximum nesting depth of arrays and objects.
+/// * `max_items`: maximum number of array elements or object members.
+///
+/// # Returns
+/// [`JsonValue`], the header fields describing the body, `Content-Type`, and the body.
+pub fn json(
+  max_depth: u32,
+  max_items: usize,
+) -> impl Strategy<Value = (JsonValue, Vec<HeaderField>, Vec<u8>)> {
+  (json_value(max_depth, max_items), json_style()).prop_map(|(value, style)| {
+    let mut body = String::new();
+    value.write(style, 0, &mut body);
+    let headers =
+      vec![HeaderField { name: CONTENT_TYPE.to_string(), value: "application/json".to_string() }];
+    (value, headers, body.into_bytes())
+  })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  pub(crate) fn json_asserts(value: &JsonValue, parsed: &serde_json::Value) {
+    match (value, parsed) {
+      (JsonValue::Null, serde_json::Value::Null) => {}
+      (JsonValue::Bool(value), serde_json::Value::Bool(parsed)) => assert_eq!(value, parsed),
+      (JsonValue::Number(value), serde_json::Value::Number(parsed)) => {
+        assert_eq!(Some(*value), parsed.as_f64());
+      }
+      (JsonValue::String(value), serde_json::Value::String(parsed)) => assert_eq!(value, parsed),
+      (JsonValue::Array(values), serde_json::Value::Array(parsed)) => {
+        assert_eq!(values.len(), parsed.len());
+        for (value, parsed) in values.iter().zip(parsed) {
+          json_asserts(value, parsed);
+        }
+      }
+      (JsonValue::Object(members), serde_json::Value::Object(parsed)) => {
+        assert_eq!(members.len(), parsed.len());
+        for (name, value) in members {
+          json_asserts(value, &parsed[name]);
+        }
+      }
+      (value, parsed) => panic!("expected {value:?} but parsed {parsed:?}"),
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn json_works((value, headers, body) in json(3, 6)) {
+      assert_eq!(headers[0].value, "application/json");
+      let parsed = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+      json_asserts(&value, &parsed);
+      let compact = serde_json::from_str::<serde_json::Value>(&value.to_string()).unwrap();
+      assert_eq!(compact, parsed);
+    }
+  }
+}
@@ -0,0 +1,93 @@
+//! HTTP message body strategies.
+//!
+//! body strategies generate a model of the content, the header fields describing the content,
+//! like `Content-Type`, and the body octets. They plug into
+//! [`request_with_body`](crate::request::request_with_body), which adds `Content-Length`.
+
+use proptest::{
+  collection::vec,
+  prelude::{Strategy, any},
+  prop_oneof,
+};
+
+use crate::{
+  body::{
+    form::{FormField, form_urlencoded},
+    json::{JsonValue, json},
+    text::{Text, text_plain},
+  },
+  header::{HeaderField, common::content_type::CONTENT_TYPE},
+};
+
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "br"))]
+pub mod encoding;
+pub mod form;
+pub mod json;
+pub mod multipart;
+pub mod text;
+
+/// body matching its declared media type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StructuredBody {
+  /// `application/json`.
+  Json(JsonValue),
+  /// `application/x-www-form-urlencoded`.
+  Form(Vec<FormField>),
+  /// `text/plain` with a charset.
+  Text(Text),
+  /// `application/octet-stream`.
+  Octets(Vec<u8>),
+}
+
+/// strategy for generating `application/octet-stream` body.
+///
+/// # Arguments
+/// * `max_len`: maximum length of the body.
+///
+/// # Returns
+/// the body, the header fields describing the body, `Content-Type`, and the body.
+pub fn octets(max_len: usize) -> impl Strategy<Value = (Vec<u8>, Vec<HeaderField>, Vec<u8>)> {
+  vec(any::<u8>(), 0..=max_len).prop_map(|body| {
+    let headers = vec![HeaderField {
+      name: CONTENT_TYPE.to_string(),
+      value: "application/octet-stream".to_string(),
+    }];
+    (body.clone(), headers, body)
+  })
+}
+
+/// strategy for generating body matching its `Content-Type`: a JSON document, form fields, a
+/// text with its charset or opaque octets.
+///
+/// # Arguments
+/// * `max_len`: maximum length of texts and octets, and maximum number of form fields, array
+///   elements and object members.
+///
+/// # Returns
+/// [`StructuredBody`], the header fields describing the body, `Content-Type`, and the body.
+pub fn structured_body(
+  max_len: usize,
+) -> impl Strategy<Value = (StructuredBody, Vec<HeaderField>, Vec<u8>)> {
+  prop_oneof![
+    json(3, max_len.min(8)).prop_map(|(value, headers, body)| (
+      StructuredBody::Json(value),
+      headers,
+      body
+    )),
+    form_urlencoded(0..=max_len.min(8)).prop_map(|(fields, headers, body)| (
+      StructuredBody::Form(fields),
+      headers,
+      body
+    )),
+    text_plain(max_len).prop_map(|(text, headers, body)| (
+      StructuredBody::Text(text),
+      headers,
+      body
+    )),
+    octets(max_len).prop_map(|(octets, headers, body)| (
+      StructuredBody::Octets(octets),
+      headers,
+      body
+    )),
+  ]
+}
@@ -0,0 +1,354 @@
+//! `multipart/form-data` body (RFC 7578) strategies.
+
+use proptest::{
+  collection::vec,
+  option::of,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+  sample::Index,
+};
+
+use crate::header::{
+  HeaderField,
+  common::{
+    any_case,
+    content_type::{CONTENT_TYPE, MediaType, media_type},
+    quote,
+  },
+};
+
+/// `Content-Disposition` header field name.
+pub const CONTENT_DISPOSITION: &str = "content-disposition";
+
+/// part of a `multipart/form-data` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+  /// form field name.
+  pub name: String,
+  /// file name, from `filename*` when present (RFC 6266 section 4.3).
+  pub filename: Option<String>,
+  pub content_type: Option<MediaType>,
+  pub content: Vec<u8>,
+}
+
+/// `multipart/form-data` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multipart {
+  pub boundary: String,
+  pub parts: Vec<Part>,
+}
+
+impl Multipart {
+  /// `Content-Type` header field of the body, the boundary being quoted when it is not a
+  /// token.
+  pub fn content_type(&self) -> HeaderField {
+    // boundary characters which are `tchar`.
+    let is_token =
+      self.boundary.bytes().all(|b| b.is_ascii_alphanumeric() || b"'+_-.".contains(&b));
+    let boundary = if is_token { self.boundary.clone() } else { quote(&self.boundary) };
+    HeaderField {
+      name: CONTENT_TYPE.to_string(),
+      value: format!("multipart/form-data; boundary={boundary}"),
+    }
+  }
+}
+
+/// strategy for generating boundary.
+///
+/// > `boundary = 0*69<bchars> bcharsnospace`
+fn boundary() -> impl Strategy<Value = String> {
+  "[0-9A-Za-z'()+_,\\-./:=? ]{0,69}[0-9A-Za-z'()+_,\\-./:=?]"
+}
+
+/// percent encode a value with the `attr-char` set (RFC 8187 section 3.2.1).
+fn ext_value_encode(value: &str) -> String {
+  let mut encoded = String::new();
+  for octet in value.bytes() {
+    if octet.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&octet) {
+      encoded.push(char::from(octet));
+    } else {
+      encoded.push_str(&format!("%{octet:02X}"));
+    }
+  }
+  encoded
+}
+
+/// strategy for generating `Content-Disposition` filename parameters.
+///
+/// # Returns
+/// file name and parameters representation, each with its leading `; `.
+fn filename() -> impl Strategy<Value = (String, String)> {
+  let quoted = || "[ !#-\\[\\]-~]{1,16}";
+  prop_oneof![
+    quoted().prop_map(|filename| {
+      let repr = format!("; filename={}", quote(&filename));
+      (filename, repr)
+    }),
+    "\\PC{1,8}".prop_map(|filename| {
+      let repr = format!("; filename*=UTF-8''{}", ext_value_encode(&filename));
+      (filename, repr)
+    }),
+    // legacy `filename` for recipients not supporting `filename*`.
+    (quoted(), "\\PC{1,8}", any::<bool>()).prop_map(|(fallback, filename, ext_first)| {
+      let fallback = format!("; filename={}", quote(&fallback));
+      let ext = format!("; filename*=utf-8''{}", ext_value_encode(&filename));
+      let repr = if ext_first { format!("{ext}{fallback}") } else { format!("{fallback}{ext}") };
+      (filename, repr)
+    }),
+  ]
+}
+
+/// near collision of the content with the boundary delimiter.
+#[derive(Debug, Clone)]
+enum NearCollision {
+  /// delimiter missing the last boundary character.
+  Truncated,
+  /// delimiter with a single dash.
+  SingleDash,
+  /// delimiter not at the beginning of a line.
+  MidLine,
+  /// delimiter with the last boundary character changed.
+  LastCharChanged,
+}
+
+impl NearCollision {
+  fn octets(&self, boundary: &str) -> Vec<u8> {
+    let (head, last) = boundary.split_at(boundary.len() - 1);
+    match self {
+      Self::Truncated => format!("\r\n--{head}"),
+      Self::SingleDash => format!("\r\n-{boundary}"),
+      Self::MidLine => format!("x--{boundary}\r\n"),
+      Self::LastCharChanged => {
+        format!("\r\n--{head}{}", if last == "a" { "b" } else { "a" })
+      }
+    }
+    .into_bytes()
+  }
+}
+
+fn near_collision() -> impl Strategy<Value = NearCollision> {
+  prop_oneof![
+    Just(NearCollision::Truncated),
+    Just(NearCollision::SingleDash),
+    Just(NearCollision::MidLine),
+    Just(NearCollision::LastCharChanged),
+  ]
+}
+
+/// random decisions for building a part.
+#[derive(Debug, Clone)]
+struct PartChoice {
+  name: String,
+  disposition_name: String,
+  disposition_type: String,
+  filename: Option<(String, String)>,
+  content_type: Option<(String, (MediaType, String))>,
+  content: Vec<u8>,
+  collisions: Vec<(Index, NearCollision)>,
+}
+
+fn part_choice(max_part_len: usize) -> impl Strategy<Value = PartChoice> {
+  (
+    "[ !#-\\[\\]-~]{0,16}",
+    any_case(CONTENT_DISPOSITION),
+    any_case("form-data"),
+    of(filename()),
+    of((any_case(CONTENT_TYPE), media_type())),
+    vec(any::<u8>(), 0..=max_part_len),
+    vec((any::<Index>(), near_collision()), 0..=2),
+  )
+    .prop_map(
+      |(name, disposition_name, disposition_type, filename, content_type, content, collisions)| {
+        PartChoice {
+          name,
+          disposition_name,
+          disposition_type,
+          filename,
+          content_type,
+          content,
+          collisions,
+        }
+      },
+    )
+}
+
+/// strategy for generating `multipart/form-data` body.
+///
+/// boundaries are random, and contents may nearly collide with the boundary delimiter. Parts
+/// have a quoted field name, an optional file name, either quoted or with the RFC 8187
+/// `filename*` encoding, an optional `Content-Type`, and binary content.
+///
+/// # Arguments
+/// * `max_parts`: maximum number of parts, at least one.
+/// * `max_part_len`: maximum length of a part content, near collisions excluded.
+///
+/// # Returns
+/// [`Multipart`], the header fields describing the body, `Content-Type`, and the body.
+pub fn multipart_form_data(
+  max_parts: usize,
+  max_part_len: usize,
+) -> impl Strategy<Value = (Multipart, Vec<HeaderField>, Vec<u8>)> {
+  (boundary(), vec(part_choice(max_part_len), 1..=max_parts.max(1))).prop_map(
+    |(boundary, choices)| {
+      let delimiter = format!("\r\n--{boundary}").into_bytes();
+      let mut body = vec![];
+      let mut parts = vec![];
+      for choice in choices {
+        let mut content = choice.content;
+        for (index, collision) in choice.collisions {
+          let position = index.index(content.len() + 1);
+          content.splice(position..position, collision.octets(&boundary));
+        }
+        // the content follows a line break, and must not contain the delimiter. Random octets may
+        // complete a near collision, the last octet of every delimiter is then dropped, which is
+        // always part of the content.
+        let mut line = b"\r\n".to_vec();
+        line.extend_from_slice(&content);
+        while let Some(position) =
+          line.windows(delimiter.len()).position(|window| window == delimiter)
+        {
+          line.remove(position + delimiter.len() - 1);
+        }
+        let content = line.split_off(2);
+
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        let mut disposition = format!(
+          "{}: {}; name={}",
+          choice.disposition_name,
+          choice.disposition_type,
+          quote(&choice.name)
+        );
+        if let Some((_, repr)) = &choice.filename {
+          disposition.push_str(repr);
+        }
+        body.extend_from_slice(disposition.as_bytes());
+        body.extend_from_slice(b"\r\n");
+        if let Some((name, (_, repr))) = &choice.content_type {
+          body.extend_from_slice(format!("{name}: {repr}\r\n").as_bytes());
+        }
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(&content);
+        body.extend_from_slice(b"\r\n");
+
+        parts.push(Part {
+          name: choice.name,
+          filename: choice.filename.map(|(filename, _)| filename),
+          content_type: choice.content_type.map(|(_, (media_type, _))| media_type),
+          content,
+        });
+      }
+      body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+      let multipart = Multipart { boundary, parts };
+      let headers = vec![multipart.content_type()];
+      (multipart, headers, body)
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::common::{
+    content_type::tests::media_type_asserts,
+    tests::{split_list, unquote},
+  };
+
+  /// decode a `filename*` value.
+  fn ext_value_decode(value: &str) -> String {
+    let (charset, encoded) = value.split_once("''").unwrap();
+    assert!(charset.eq_ignore_ascii_case("utf-8"));
+    let mut octets = vec![];
+    let mut bytes = encoded.bytes();
+    while let Some(octet) = bytes.next() {
+      if octet == b'%' {
+        let hex = [bytes.next().unwrap(), bytes.next().unwrap()];
+        octets.push(u8::from_str_radix(str::from_utf8(&hex).unwrap(), 16).unwrap());
+      } else {
+        octets.push(octet);
+      }
+    }
+    String::from_utf8(octets).unwrap()
+  }
+
+  /// parse a part following RFC 7578.
+  fn parse_part(input: &[u8], expected: &Part) {
+    let end = input.windows(4).position(|window| window == b"\r\n\r\n").unwrap();
+    let (head, content) = (&input[..end], &input[end + 4..]);
+    assert_eq!(content, expected.content);
+
+    let mut content_type = None;
+    let mut disposition = None;
+    for line in str::from_utf8(head).unwrap().split("\r\n") {
+      let (name, value) = line.split_once(": ").unwrap();
+      match name.to_ascii_lowercase().as_str() {
+        CONTENT_DISPOSITION => disposition = Some(value),
+        CONTENT_TYPE => content_type = Some(value),
+        name => panic!("unexpected part header {name:?}"),
+      }
+    }
+
+    let mut params = split_list(disposition.unwrap(), ';').into_iter();
+    assert!(params.next().unwrap().eq_ignore_ascii_case("form-data"));
+    let (mut name, mut filename, mut ext_filename) = (None, None, None);
+    for param in params {
+      let (param, value) = param.split_once('=').unwrap();
+      match param {
+        "name" => name = Some(unquote(value)),
+        "filename" => filename = Some(unquote(value)),
+        "filename*" => ext_filename = Some(ext_value_decode(value)),
+        param => panic!("unexpected parameter {param:?}"),
+      }
+    }
+    assert_eq!(name.as_ref(), Some(&expected.name));
+    assert_eq!(ext_filename.or(filename), expected.filename);
+
+    match (content_type, &expected.content_type) {
+      (Some(repr), Some(media_type)) => media_type_asserts(media_type, repr),
+      (None, None) => {}
+      (repr, media_type) => panic!("expected {media_type:?} but got {repr:?}"),
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn multipart_form_data_works((multipart, headers, body) in multipart_form_data(4, 64)) {
+      assert_eq!(headers.len(), 1);
+      assert_eq!(headers[0].name, CONTENT_TYPE);
+      let params = split_list(&headers[0].value, ';');
+      assert_eq!(params[0], "multipart/form-data");
+      let boundary = unquote(params[1].strip_prefix("boundary=").unwrap());
+      assert_eq!(boundary, multipart.boundary);
+
+      // the first delimiter has no leading line break.
+      let input = [b"\r\n".as_slice(), &body].concat();
+      let delimiter = format!("\r\n--{boundary}").into_bytes();
+      let mut positions = vec![];
+      let mut start = 0;
+      while let Some(position) =
+        input[start..].windows(delimiter.len()).position(|window| window == delimiter)
+      {
+        positions.push(start + position);
+        start += position + delimiter.len();
+      }
+      assert_eq!(positions.len(), multipart.parts.len() + 1);
+      assert_eq!(&input[*positions.last().unwrap() + delimiter.len()..], b"--\r\n");
+
+      for part in &multipart.parts {
+        let line = [b"\r\n".as_slice(), &part.content].concat();
+        assert!(
+          !line.windows(delimiter.len()).any(|window| window == delimiter),
+          "part content {:?} contains the delimiter",
+          part.content
+        );
+      }
+      for (window, part) in positions.windows(2).zip(&multipart.parts) {
+        let encapsulation = &input[window[0] + delimiter.len()..window[1]];
+        let encapsulation = encapsulation.strip_prefix(b"\r\n").unwrap();
+        parse_part(encapsulation, part);
+      }
+    }
+  }
+}
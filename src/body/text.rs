@@ -0,0 +1,103 @@
+//! `text/plain` body strategies.
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+use crate::header::{
+  HeaderField,
+  common::{any_case, content_type::CONTENT_TYPE},
+};
+
+/// character encoding of a text body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+  Utf8,
+  UsAscii,
+  /// ISO-8859-1, every character is a single octet.
+  Latin1,
+}
+
+impl Charset {
+  /// lower cased charset name.
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Utf8 => "utf-8",
+      Self::UsAscii => "us-ascii",
+      Self::Latin1 => "iso-8859-1",
+    }
+  }
+
+  /// encode a text which characters are all in the charset.
+  pub fn encode(self, text: &str) -> Vec<u8> {
+    match self {
+      Self::Utf8 | Self::UsAscii => text.as_bytes().to_vec(),
+      Self::Latin1 => text.chars().map(|c| u8::try_from(c).expect("latin-1 character")).collect(),
+    }
+  }
+}
+
+/// text body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Text {
+  pub charset: Charset,
+  pub text: String,
+}
+
+/// strategy for generating `text/plain` body with a charset.
+///
+/// # Arguments
+/// * `max_len`: maximum number of characters.
+///
+/// # Returns
+/// [`Text`], the header fields describing the body, `Content-Type` with a charset in random
+/// case, and the encoded text.
+pub fn text_plain(max_len: usize) -> impl Strategy<Value = (Text, Vec<HeaderField>, Vec<u8>)> {
+  let text = move |chars: &'static str, charset: Charset| {
+    vec(chars, 0..=max_len).prop_map(move |chars| Text { charset, text: chars.concat() })
+  };
+  prop_oneof![
+    text("[\\PC\r\n\t]", Charset::Utf8),
+    text("[ -~\r\n\t]", Charset::UsAscii),
+    text("[ -~\u{a0}-\u{ff}\r\n\t]", Charset::Latin1),
+  ]
+  .prop_flat_map(|text| {
+    let charset = any_case(text.charset.name());
+    (Just(text), charset)
+  })
+  .prop_map(|(text, charset_repr)| {
+    let body = text.charset.encode(&text.text);
+    let headers = vec![HeaderField {
+      name: CONTENT_TYPE.to_string(),
+      value: format!("text/plain; charset={charset_repr}"),
+    }];
+    (text, headers, body)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  proptest! {
+    #[test]
+    fn text_plain_works((text, headers, body) in text_plain(64)) {
+      let charset = headers[0].value.strip_prefix("text/plain; charset=").unwrap();
+      let decoded = match charset.to_ascii_lowercase().as_str() {
+        "utf-8" => String::from_utf8(body).unwrap(),
+        "us-ascii" => {
+          assert!(body.is_ascii());
+          String::from_utf8(body).unwrap()
+        }
+        "iso-8859-1" => body.into_iter().map(char::from).collect(),
+        charset => panic!("unexpected charset {charset:?}"),
+      };
+      assert_eq!(charset.to_ascii_lowercase(), text.charset.name());
+      assert_eq!(decoded, text.text);
+    }
+  }
+}
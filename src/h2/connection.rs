@@ -0,0 +1,505 @@
+//! HTTP/2 client connection strategies.
+//!
+//! a connection starts with the client preface and SETTINGS, then carries one or more requests
+//! on their own streams. Requests reuse the [`request_line`](crate::request_line) model through
+//! their pseudo header fields.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  bool::weighted,
+  collection::vec,
+  option::of,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+  sample::Index,
+};
+
+use crate::{
+  h2::{
+    frame::{
+      DEFAULT_MAX_FRAME_SIZE, DEFAULT_WINDOW_SIZE, ErrorCode, Frame, MAX_WINDOW_SIZE, Priority,
+      Setting, SettingId, padding, setting,
+    },
+    hpack::{HeaderBlock, encode_header_lists},
+  },
+  header::{HeaderField, header_fields},
+  request_line::{
+    pseudo_headers::{PseudoHeaders, pseudo_headers},
+    version::HttpVersion,
+  },
+};
+
+/// client connection preface.
+pub const CONNECTION_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// connection-specific header fields which must not be used in HTTP/2 (RFC 9113 section
+/// 8.2.2).
+pub(crate) const CONNECTION_SPECIFIC_HEADERS: [&str; 5] =
+  ["connection", "keep-alive", "proxy-connection", "transfer-encoding", "upgrade"];
+
+/// largest header block fragment or data chunk, leaving room for padding and priority.
+const MAX_CHUNK_LEN: usize = DEFAULT_MAX_FRAME_SIZE - 262;
+
+/// request sent on a stream.
+#[derive(Debug, Clone)]
+pub struct Request {
+  pub stream_id: u32,
+  pub pseudo_headers: PseudoHeaders,
+  /// regular header fields, following the pseudo header fields.
+  pub headers: Vec<HeaderField>,
+  pub body: Vec<u8>,
+  /// whether the stream is reset with `CANCEL` after the request is sent.
+  pub reset: bool,
+}
+
+impl Request {
+  /// header list of the request: pseudo header fields followed by regular header fields.
+  pub fn header_list(&self) -> Vec<HeaderField> {
+    self
+      .pseudo_headers
+      .fields()
+      .into_iter()
+      .map(|(name, value)| HeaderField { name: name.to_string(), value })
+      .chain(self.headers.iter().cloned())
+      .collect()
+  }
+}
+
+/// HTTP/2 client connection.
+#[derive(Debug, Clone)]
+pub struct Connection {
+  /// client SETTINGS parameters.
+  pub settings: Vec<Setting>,
+  pub requests: Vec<Request>,
+  /// HPACK header block of every request.
+  pub header_blocks: Vec<HeaderBlock>,
+  /// frames following the preface, in order.
+  pub frames: Vec<Frame>,
+}
+
+/// control frame sent while a stream is open.
+#[derive(Debug, Clone)]
+enum Control {
+  Ping([u8; 8]),
+  WindowUpdate { connection: bool, increment: u32 },
+  Priority(Priority),
+}
+
+fn control() -> impl Strategy<Value = Control> {
+  prop_oneof![
+    any::<[u8; 8]>().prop_map(Control::Ping),
+    (any::<bool>(), 1..=u32::from(u16::MAX))
+      .prop_map(|(connection, increment)| Control::WindowUpdate { connection, increment }),
+    (any::<bool>(), 0u32..(1 << 31), any::<u8>()).prop_map(|(exclusive, dependency, weight)| {
+      Control::Priority(Priority { exclusive, dependency, weight })
+    }),
+  ]
+}
+
+/// random framing decisions for a stream.
+#[derive(Debug, Clone)]
+struct StreamChoice {
+  header_splits: Vec<Index>,
+  data_splits: Vec<Index>,
+  header_padding: Option<u8>,
+  data_padding: Option<u8>,
+  priority: Option<(bool, u32, u8)>,
+  controls: Vec<Control>,
+  reset: bool,
+}
+
+fn stream_choice() -> impl Strategy<Value = StreamChoice> {
+  (
+    vec(any::<Index>(), 0..=3),
+    vec(any::<Index>(), 0..=4),
+    padding(),
+    padding(),
+    of((any::<bool>(), 0u32..(1 << 31), any::<u8>())),
+    vec(control(), 0..=2),
+    weighted(0.1),
+  )
+    .prop_map(
+      |(header_splits, data_splits, header_padding, data_padding, priority, controls, reset)| {
+        StreamChoice {
+          header_splits,
+          data_splits,
+          header_padding,
+          data_padding,
+          priority,
+          controls,
+          reset,
+        }
+      },
+    )
+}
+
+/// split octets at the given positions, chunks are at most [`MAX_CHUNK_LEN`] long.
+///
+/// chunks are never empty, unless the input is.
+fn split(input: &[u8], splits: &[Index]) -> Vec<Vec<u8>> {
+  let mut positions = splits.iter().map(|split| split.index(input.len() + 1)).collect::<Vec<_>>();
+  positions.push(input.len());
+  positions.sort_unstable();
+
+  let mut chunks = vec![];
+  let mut start = 0;
+  for end in positions {
+    chunks.extend(input[start..end].chunks(MAX_CHUNK_LEN).map(<[u8]>::to_vec));
+    start = end;
+  }
+  if chunks.is_empty() {
+    chunks.push(vec![]);
+  }
+  chunks
+}
+
+/// drop the WINDOW_UPDATE controls which would make a flow control window exceed
+/// [`MAX_WINDOW_SIZE`], a FLOW_CONTROL_ERROR (RFC 9113 section 6.9.1).
+///
+/// stream windows start at the last `SETTINGS_INITIAL_WINDOW_SIZE` sent by the client, and
+/// the windows are never consumed since the server sends no DATA.
+fn drop_overflowing_updates(settings: &[Setting], choices: &mut [StreamChoice]) {
+  let initial_window_size = settings
+    .iter()
+    .rev()
+    .find(|setting| setting.id == SettingId::InitialWindowSize)
+    .map_or(DEFAULT_WINDOW_SIZE, |setting| setting.value);
+  let mut connection_window = DEFAULT_WINDOW_SIZE;
+  for choice in choices {
+    let mut stream_window = initial_window_size;
+    choice.controls.retain(|control| match control {
+      Control::WindowUpdate { connection, increment } => {
+        let window = if *connection { &mut connection_window } else { &mut stream_window };
+        match window.checked_add(*increment).filter(|window| *window <= MAX_WINDOW_SIZE) {
+          Some(updated) => {
+            *window = updated;
+            true
+          }
+          None => false,
+        }
+      }
+      Control::Ping(_) | Control::Priority(_) => true,
+    });
+  }
+}
+
+/// frames of a stream, grouped so that a header block is never interleaved with other frames.
+fn stream_frames(request: &Request, block: &[u8], choice: &StreamChoice) -> Vec<Vec<Frame>> {
+  let stream_id = request.stream_id;
+  let body_end_stream = request.body.is_empty();
+
+  let mut fragments = split(block, &choice.header_splits).into_iter();
+  let first = fragments.next().unwrap_or_default();
+  let fragments = fragments.collect::<Vec<_>>();
+  let mut header_frames = vec![Frame::Headers {
+    stream_id,
+    end_stream: body_end_stream,
+    end_headers: fragments.is_empty(),
+    padding: choice.header_padding,
+    priority: choice.priority.map(|(exclusive, dependency, weight)| Priority {
+      exclusive,
+      dependency: if dependency == stream_id { 0 } else { dependency },
+      weight,
+    }),
+    fragment: first,
+  }];
+  let fragment_count = fragments.len();
+  for (i, fragment) in fragments.into_iter().enumerate() {
+    header_frames.push(Frame::Continuation {
+      stream_id,
+      end_headers: i + 1 == fragment_count,
+      fragment,
+    });
+  }
+
+  let mut groups = vec![header_frames];
+  if !request.body.is_empty() {
+    let chunks = split(&request.body, &choice.data_splits);
+    let chunk_count = chunks.len();
+    for (i, data) in chunks.into_iter().enumerate() {
+      groups.push(vec![Frame::Data {
+        stream_id,
+        end_stream: i + 1 == chunk_count,
+        padding: if i == 0 { choice.data_padding } else { None },
+        data,
+      }]);
+    }
+  }
+
+  for control in &choice.controls {
+    groups.push(vec![match control {
+      Control::Ping(data) => Frame::Ping { ack: false, data: *data },
+      Control::WindowUpdate { connection, increment } => Frame::WindowUpdate {
+        stream_id: if *connection { 0 } else { stream_id },
+        increment: *increment,
+      },
+      Control::Priority(priority) => Frame::Priority {
+        stream_id,
+        priority: Priority {
+          dependency: if priority.dependency == stream_id { 0 } else { priority.dependency },
+          ..*priority
+        },
+      },
+    }]);
+  }
+
+  if request.reset {
+    groups.push(vec![Frame::RstStream { stream_id, error_code: ErrorCode::Cancel }]);
+  }
+  groups
+}
+
+/// interleave the frames of streams.
+///
+/// frames of a stream keep their order and streams are opened in increasing stream identifier
+/// order, so the header blocks are also decoded in order.
+fn interleave(streams: Vec<Vec<Vec<Frame>>>, picks: &[Index]) -> Vec<Frame> {
+  let mut streams = streams.into_iter().map(|groups| groups.into_iter()).collect::<Vec<_>>();
+  let mut opened = 0;
+  let mut frames = vec![];
+  let mut picks = picks.iter();
+
+  loop {
+    let mut candidates =
+      (0..opened).filter(|i| !streams[*i].as_slice().is_empty()).collect::<Vec<_>>();
+    if opened < streams.len() {
+      candidates.push(opened);
+    }
+    if candidates.is_empty() {
+      return frames;
+    }
+
+    let stream =
+      picks.next().map_or(candidates[0], |pick| candidates[pick.index(candidates.len())]);
+    if stream == opened {
+      opened += 1;
+    }
+    frames.extend(streams[stream].next().expect("stream with frames"));
+  }
+}
+
+/// strategy for generating request headers, without connection-specific headers.
+pub(crate) fn request_headers() -> impl Strategy<Value = Vec<HeaderField>> {
+  header_fields(0..=8).prop_map(|(fields, _)| {
+    fields
+      .into_iter()
+      .filter(|field| {
+        !CONNECTION_SPECIFIC_HEADERS.contains(&field.name.as_str())
+          && !matches!(field.name.as_str(), "te" | "host")
+      })
+      .collect()
+  })
+}
+
+/// strategy for generating HTTP/2 client connection.
+///
+/// the connection starts with the client preface and a SETTINGS frame, optionally followed by
+/// the acknowledgment of the server SETTINGS. Every request is sent on its own stream as a
+/// header block split in HEADERS and CONTINUATION frames, followed by its body in DATA frames.
+/// Streams are interleaved with each other and with PING, WINDOW_UPDATE, PRIORITY and
+/// RST_STREAM frames. The connection may end with a GOAWAY frame.
+///
+/// frames never exceed the default `SETTINGS_MAX_FRAME_SIZE` and bodies fit in the default flow
+/// control windows. WINDOW_UPDATE frames never grow a window beyond 2^31-1. Requests with a
+/// `:protocol` pseudo header field are sent as if the server had advertised
+/// `SETTINGS_ENABLE_CONNECT_PROTOCOL` (RFC 8441).
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `stream_count_range`: range of the number of streams.
+/// * `max_body_len`: maximum length of a request body.
+///
+/// # Returns
+/// [`Connection`] and its encoding.
+pub fn connection(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  stream_count_range: RangeInclusive<usize>,
+  max_body_len: usize,
+) -> impl Strategy<Value = (Connection, Vec<u8>)> {
+  // bodies and DATA padding of all streams must fit in the connection window.
+  let max_body_len = max_body_len
+    .min((DEFAULT_WINDOW_SIZE as usize / (*stream_count_range.end()).max(1)).saturating_sub(256));
+
+  let request = (
+    pseudo_headers(max_label_count, max_segments, query_count_range),
+    request_headers(),
+    vec(any::<u8>(), 0..=max_body_len),
+    stream_choice(),
+  );
+
+  (
+    vec(setting(), 0..=6),
+    any::<bool>(),
+    vec(request, stream_count_range),
+    vec(any::<Index>(), 0..=64),
+    weighted(0.3),
+  )
+    .prop_flat_map(|(settings, settings_ack, requests, picks, goaway)| {
+      let mut choices = Vec::with_capacity(requests.len());
+      let requests = (0u32..)
+        .zip(requests)
+        .map(|(i, ((mut pseudo_headers, _), headers, body, choice))| {
+          pseudo_headers.version = HttpVersion::Http2;
+          let body = if pseudo_headers.method.allows_body() { body } else { vec![] };
+          let reset = choice.reset;
+          choices.push(choice);
+          Request { stream_id: 2 * i + 1, pseudo_headers, headers, body, reset }
+        })
+        .collect::<Vec<_>>();
+
+      let lists = requests.iter().map(Request::header_list).collect();
+
+      (Just((settings, settings_ack, requests, choices, picks, goaway)), encode_header_lists(lists))
+    })
+    .prop_map(
+      |(
+        (settings, settings_ack, requests, mut choices, picks, goaway),
+        (header_blocks, blocks),
+      )| {
+        drop_overflowing_updates(&settings, &mut choices);
+        let mut frames = vec![Frame::Settings { ack: false, settings: settings.clone() }];
+        if settings_ack {
+          frames.push(Frame::Settings { ack: true, settings: vec![] });
+        }
+
+        let streams = requests
+          .iter()
+          .zip(&blocks)
+          .zip(&choices)
+          .map(|((request, block), choice)| stream_frames(request, block, choice))
+          .collect();
+        frames.extend(interleave(streams, &picks));
+
+        if goaway {
+          frames.push(Frame::GoAway {
+            last_stream_id: 0,
+            error_code: ErrorCode::NoError,
+            debug_data: vec![],
+          });
+        }
+
+        let mut repr = CONNECTION_PREFACE.to_vec();
+        for frame in &frames {
+          frame.encode(&mut repr);
+        }
+
+        (Connection { settings, requests, header_blocks, frames }, repr)
+      },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use loona_hpack::Decoder;
+  use proptest::proptest;
+
+  use super::*;
+  use crate::h2::{
+    frame::tests::{parse_frames, unpadded},
+    hpack::tests::decoded_fields,
+  };
+
+  proptest! {
+    #[test]
+    fn connection_works(
+      (connection, repr) in connection(20, 50.try_into().unwrap(), 0..=10, 1..=5, 1024)
+    ) {
+      assert!(repr.starts_with(CONNECTION_PREFACE));
+      let frames = parse_frames(&repr[CONNECTION_PREFACE.len()..]);
+      assert_eq!(frames.len(), connection.frames.len());
+      assert_eq!(frames[0].frame_type, 0x4, "first frame should be SETTINGS");
+
+      let mut decoder = Decoder::new();
+      let mut header_block: Option<(u32, Vec<u8>)> = None;
+      let mut decoded = HashMap::new();
+      let mut bodies: HashMap<u32, Vec<u8>> = HashMap::new();
+      let mut ended = vec![];
+      let mut last_stream_id = 0;
+      let mut flow = 0;
+      let mut initial_window_size = u64::from(DEFAULT_WINDOW_SIZE);
+      let mut windows: HashMap<u32, u64> = HashMap::new();
+
+      for frame in frames {
+        assert!(frame.payload.len() <= DEFAULT_MAX_FRAME_SIZE, "frame too large");
+        if let Some((stream_id, _)) = header_block.as_ref() {
+          assert_eq!(frame.frame_type, 0x9, "expected CONTINUATION");
+          assert_eq!(frame.stream_id, *stream_id);
+        }
+        match frame.frame_type {
+          0x1 | 0x9 => {
+            let fragment = if frame.frame_type == 0x1 {
+              assert!(frame.stream_id > last_stream_id, "streams should be opened in order");
+              last_stream_id = frame.stream_id;
+              header_block = Some((frame.stream_id, vec![]));
+              unpadded(&frame)
+            } else {
+              frame.payload
+            };
+            header_block.as_mut().unwrap().1.extend_from_slice(fragment);
+            if frame.flags & 0x4 != 0 {
+              let (stream_id, block) = header_block.take().unwrap();
+              decoded.insert(stream_id, decoded_fields(decoder.decode(&block).unwrap()));
+            }
+            if frame.frame_type == 0x1 && frame.flags & 0x1 != 0 {
+              ended.push(frame.stream_id);
+            }
+          }
+          0x0 => {
+            assert!(decoded.contains_key(&frame.stream_id), "DATA before HEADERS");
+            assert!(!ended.contains(&frame.stream_id), "DATA after END_STREAM");
+            flow += frame.payload.len();
+            bodies.entry(frame.stream_id).or_default().extend_from_slice(unpadded(&frame));
+            if frame.flags & 0x1 != 0 {
+              ended.push(frame.stream_id);
+            }
+          }
+          0x4 => {
+            for setting in frame.payload.chunks(6) {
+              if setting[..2] == [0x0, 0x4] {
+                initial_window_size = u64::from(u32::from_be_bytes(setting[2..].try_into().unwrap()));
+              }
+            }
+          }
+          0x8 => {
+            assert!(frame.stream_id == 0 || frame.stream_id % 2 == 1);
+            let increment = u32::from_be_bytes(frame.payload[..4].try_into().unwrap());
+            let initial = if frame.stream_id == 0 {
+              u64::from(DEFAULT_WINDOW_SIZE)
+            } else {
+              initial_window_size
+            };
+            let window = windows.entry(frame.stream_id).or_insert(initial);
+            *window += u64::from(increment & MAX_WINDOW_SIZE);
+            assert!(*window <= u64::from(MAX_WINDOW_SIZE), "window of {} overflowed", frame.stream_id);
+          }
+          _ => assert!(
+            frame.stream_id == 0 || frame.stream_id % 2 == 1,
+            "unexpected server stream {}",
+            frame.stream_id
+          ),
+        }
+      }
+      assert!(flow <= DEFAULT_WINDOW_SIZE as usize, "flow control window exceeded");
+
+      for request in &connection.requests {
+        assert!(ended.contains(&request.stream_id), "stream {} not ended", request.stream_id);
+        let fields = &decoded[&request.stream_id];
+        let pseudo_fields = request.pseudo_headers.fields();
+        assert_eq!(fields.len(), pseudo_fields.len() + request.headers.len());
+        for ((name, value), field) in pseudo_fields.iter().zip(fields) {
+          assert_eq!(*name, field.name);
+          assert_eq!(*value, field.value);
+        }
+        assert_eq!(&fields[pseudo_fields.len()..], request.headers.as_slice());
+        assert_eq!(bodies.get(&request.stream_id).cloned().unwrap_or_default(), request.body);
+      }
+    }
+  }
+}
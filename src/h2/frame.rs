@@ -0,0 +1,580 @@
+//! HTTP/2 frame (RFC 9113 section 6) strategies.
+
+use std::ops::RangeInclusive;
+
+use proptest::{
+  collection::vec,
+  option::of,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+};
+
+use crate::h2::hpack::header_block;
+
+/// length of the frame header.
+pub const FRAME_HEADER_LEN: usize = 9;
+/// default and minimum `SETTINGS_MAX_FRAME_SIZE`.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16_384;
+/// default `SETTINGS_INITIAL_WINDOW_SIZE` and initial connection flow control window.
+pub const DEFAULT_WINDOW_SIZE: u32 = 65_535;
+/// maximum flow control window size.
+pub const MAX_WINDOW_SIZE: u32 = (1 << 31) - 1;
+
+const DATA: u8 = 0x0;
+const HEADERS: u8 = 0x1;
+const PRIORITY: u8 = 0x2;
+const RST_STREAM: u8 = 0x3;
+const SETTINGS: u8 = 0x4;
+const PING: u8 = 0x6;
+const GOAWAY: u8 = 0x7;
+const WINDOW_UPDATE: u8 = 0x8;
+const CONTINUATION: u8 = 0x9;
+
+const END_STREAM_FLAG: u8 = 0x1;
+const ACK_FLAG: u8 = 0x1;
+const END_HEADERS_FLAG: u8 = 0x4;
+const PADDED_FLAG: u8 = 0x8;
+const PRIORITY_FLAG: u8 = 0x20;
+
+/// HTTP/2 error codes (RFC 9113 section 7).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+  NoError = 0x0,
+  ProtocolError = 0x1,
+  InternalError = 0x2,
+  FlowControlError = 0x3,
+  SettingsTimeout = 0x4,
+  StreamClosed = 0x5,
+  FrameSizeError = 0x6,
+  RefusedStream = 0x7,
+  Cancel = 0x8,
+  CompressionError = 0x9,
+  ConnectError = 0xa,
+  EnhanceYourCalm = 0xb,
+  InadequateSecurity = 0xc,
+  Http11Required = 0xd,
+}
+
+/// SETTINGS parameter identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingId {
+  HeaderTableSize,
+  EnablePush,
+  MaxConcurrentStreams,
+  InitialWindowSize,
+  MaxFrameSize,
+  MaxHeaderListSize,
+  /// `SETTINGS_ENABLE_CONNECT_PROTOCOL` (RFC 8441).
+  EnableConnectProtocol,
+  /// unknown identifiers must be ignored.
+  Unknown(u16),
+}
+
+impl SettingId {
+  fn code(self) -> u16 {
+    match self {
+      SettingId::HeaderTableSize => 0x1,
+      SettingId::EnablePush => 0x2,
+      SettingId::MaxConcurrentStreams => 0x3,
+      SettingId::InitialWindowSize => 0x4,
+      SettingId::MaxFrameSize => 0x5,
+      SettingId::MaxHeaderListSize => 0x6,
+      SettingId::EnableConnectProtocol => 0x8,
+      SettingId::Unknown(code) => code,
+    }
+  }
+}
+
+/// SETTINGS parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setting {
+  pub id: SettingId,
+  pub value: u32,
+}
+
+/// stream priority (deprecated by RFC 9113 but still valid on the wire).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Priority {
+  pub exclusive: bool,
+  pub dependency: u32,
+  /// weight minus one, as sent on the wire.
+  pub weight: u8,
+}
+
+/// HTTP/2 frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+  Data {
+    stream_id: u32,
+    end_stream: bool,
+    padding: Option<u8>,
+    data: Vec<u8>,
+  },
+  Headers {
+    stream_id: u32,
+    end_stream: bool,
+    end_headers: bool,
+    padding: Option<u8>,
+    priority: Option<Priority>,
+    fragment: Vec<u8>,
+  },
+  Priority {
+    stream_id: u32,
+    priority: Priority,
+  },
+  RstStream {
+    stream_id: u32,
+    error_code: ErrorCode,
+  },
+  Settings {
+    ack: bool,
+    settings: Vec<Setting>,
+  },
+  Ping {
+    ack: bool,
+    data: [u8; 8],
+  },
+  GoAway {
+    last_stream_id: u32,
+    error_code: ErrorCode,
+    debug_data: Vec<u8>,
+  },
+  WindowUpdate {
+    stream_id: u32,
+    increment: u32,
+  },
+  Continuation {
+    stream_id: u32,
+    end_headers: bool,
+    fragment: Vec<u8>,
+  },
+}
+
+fn flag(set: bool, flag: u8) -> u8 {
+  if set { flag } else { 0 }
+}
+
+fn encode_priority(priority: &Priority, output: &mut Vec<u8>) {
+  let dependency = priority.dependency | if priority.exclusive { 1 << 31 } else { 0 };
+  output.extend_from_slice(&dependency.to_be_bytes());
+  output.push(priority.weight);
+}
+
+/// encode padded payload: pad length, payload and padding octets.
+fn encode_padded(padding: Option<u8>, payload: &[u8], output: &mut Vec<u8>) {
+  if let Some(padding) = padding {
+    output.push(padding);
+  }
+  output.extend_from_slice(payload);
+  output.extend(std::iter::repeat_n(0, usize::from(padding.unwrap_or_default())));
+}
+
+impl Frame {
+  /// stream identifier of the frame, `0` for connection frames.
+  pub fn stream_id(&self) -> u32 {
+    match self {
+      Frame::Data { stream_id, .. }
+      | Frame::Headers { stream_id, .. }
+      | Frame::Priority { stream_id, .. }
+      | Frame::RstStream { stream_id, .. }
+      | Frame::WindowUpdate { stream_id, .. }
+      | Frame::Continuation { stream_id, .. } => *stream_id,
+      Frame::Settings { .. } | Frame::Ping { .. } | Frame::GoAway { .. } => 0,
+    }
+  }
+
+  /// encode the frame, header included.
+  pub fn encode(&self, output: &mut Vec<u8>) {
+    let mut payload = vec![];
+    let (frame_type, flags) = match self {
+      Frame::Data { end_stream, padding, data, .. } => {
+        encode_padded(*padding, data, &mut payload);
+        (DATA, flag(*end_stream, END_STREAM_FLAG) | flag(padding.is_some(), PADDED_FLAG))
+      }
+      Frame::Headers { end_stream, end_headers, padding, priority, fragment, .. } => {
+        let mut block = vec![];
+        if let Some(priority) = priority {
+          encode_priority(priority, &mut block);
+        }
+        block.extend_from_slice(fragment);
+        encode_padded(*padding, &block, &mut payload);
+        (
+          HEADERS,
+          flag(*end_stream, END_STREAM_FLAG)
+            | flag(*end_headers, END_HEADERS_FLAG)
+            | flag(padding.is_some(), PADDED_FLAG)
+            | flag(priority.is_some(), PRIORITY_FLAG),
+        )
+      }
+      Frame::Priority { priority, .. } => {
+        encode_priority(priority, &mut payload);
+        (PRIORITY, 0)
+      }
+      Frame::RstStream { error_code, .. } => {
+        payload.extend_from_slice(&(*error_code as u32).to_be_bytes());
+        (RST_STREAM, 0)
+      }
+      Frame::Settings { ack, settings } => {
+        for setting in settings {
+          payload.extend_from_slice(&setting.id.code().to_be_bytes());
+          payload.extend_from_slice(&setting.value.to_be_bytes());
+        }
+        (SETTINGS, flag(*ack, ACK_FLAG))
+      }
+      Frame::Ping { ack, data } => {
+        payload.extend_from_slice(data);
+        (PING, flag(*ack, ACK_FLAG))
+      }
+      Frame::GoAway { last_stream_id, error_code, debug_data } => {
+        payload.extend_from_slice(&last_stream_id.to_be_bytes());
+        payload.extend_from_slice(&(*error_code as u32).to_be_bytes());
+        payload.extend_from_slice(debug_data);
+        (GOAWAY, 0)
+      }
+      Frame::WindowUpdate { increment, .. } => {
+        payload.extend_from_slice(&increment.to_be_bytes());
+        (WINDOW_UPDATE, 0)
+      }
+      Frame::Continuation { end_headers, fragment, .. } => {
+        payload.extend_from_slice(fragment);
+        (CONTINUATION, flag(*end_headers, END_HEADERS_FLAG))
+      }
+    };
+
+    output.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    output.push(frame_type);
+    output.push(flags);
+    output.extend_from_slice(&self.stream_id().to_be_bytes());
+    output.extend_from_slice(&payload);
+  }
+
+  /// encoded frame, header included.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut output = vec![];
+    self.encode(&mut output);
+    output
+  }
+}
+
+/// strategy for generating client initiated stream identifier, which is odd.
+pub fn client_stream_id() -> impl Strategy<Value = u32> {
+  (0u32..(1 << 30)).prop_map(|n| 2 * n + 1)
+}
+
+/// strategy for generating padding length.
+pub(crate) fn padding() -> impl Strategy<Value = Option<u8>> {
+  prop_oneof![3 => Just(None), 1 => of(any::<u8>())]
+}
+
+/// strategy for generating stream priority.
+///
+/// # Arguments
+/// * `stream_id`: stream the priority applies to, a stream cannot depend on itself.
+pub fn priority(stream_id: u32) -> impl Strategy<Value = Priority> {
+  (any::<bool>(), 0u32..(1 << 31), any::<u8>())
+    .prop_filter("stream depending on itself", move |(_, dependency, _)| *dependency != stream_id)
+    .prop_map(|(exclusive, dependency, weight)| Priority { exclusive, dependency, weight })
+}
+
+/// strategy for generating valid SETTINGS parameter.
+pub fn setting() -> impl Strategy<Value = Setting> {
+  prop_oneof![
+    (0..=u32::MAX).prop_map(|value| Setting { id: SettingId::HeaderTableSize, value }),
+    (0..=1u32).prop_map(|value| Setting { id: SettingId::EnablePush, value }),
+    (0..=u32::MAX).prop_map(|value| Setting { id: SettingId::MaxConcurrentStreams, value }),
+    // windows close to the maximum, which WINDOW_UPDATE frames can overflow.
+    prop_oneof![0..=MAX_WINDOW_SIZE, MAX_WINDOW_SIZE - DEFAULT_WINDOW_SIZE..=MAX_WINDOW_SIZE]
+      .prop_map(|value| Setting { id: SettingId::InitialWindowSize, value }),
+    (DEFAULT_MAX_FRAME_SIZE as u32..=(1 << 24) - 1)
+      .prop_map(|value| Setting { id: SettingId::MaxFrameSize, value }),
+    (0..=u32::MAX).prop_map(|value| Setting { id: SettingId::MaxHeaderListSize, value }),
+    (0..=1u32).prop_map(|value| Setting { id: SettingId::EnableConnectProtocol, value }),
+    (0x10..=u16::MAX, any::<u32>())
+      .prop_map(|(code, value)| Setting { id: SettingId::Unknown(code), value }),
+  ]
+}
+
+/// strategy for generating DATA frame.
+///
+/// # Arguments
+/// * `stream_id`: stream of the frame.
+/// * `len_range`: range of the data length.
+///
+/// # Returns
+/// [`Frame::Data`] and its encoding.
+pub fn data_frame(
+  stream_id: u32,
+  len_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (any::<bool>(), padding(), vec(any::<u8>(), len_range)).prop_map(
+    move |(end_stream, padding, data)| {
+      let frame = Frame::Data { stream_id, end_stream, padding, data };
+      let repr = frame.to_bytes();
+      (frame, repr)
+    },
+  )
+}
+
+/// strategy for generating HEADERS frame carrying a complete HPACK header block.
+///
+/// # Arguments
+/// * `stream_id`: stream of the frame.
+/// * `count_range`: range of the number of header fields.
+///
+/// # Returns
+/// [`Frame::Headers`] and its encoding.
+pub fn headers_frame(
+  stream_id: u32,
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (any::<bool>(), padding(), of(priority(stream_id)), header_block(count_range)).prop_map(
+    move |(end_stream, padding, priority, (_, fragment))| {
+      let frame =
+        Frame::Headers { stream_id, end_stream, end_headers: true, padding, priority, fragment };
+      let repr = frame.to_bytes();
+      (frame, repr)
+    },
+  )
+}
+
+/// strategy for generating CONTINUATION frame with an arbitrary header block fragment.
+///
+/// # Arguments
+/// * `stream_id`: stream of the frame.
+///
+/// # Returns
+/// [`Frame::Continuation`] and its encoding.
+pub fn continuation_frame(stream_id: u32) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (any::<bool>(), vec(any::<u8>(), 0..=256)).prop_map(move |(end_headers, fragment)| {
+    let frame = Frame::Continuation { stream_id, end_headers, fragment };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating PRIORITY frame.
+///
+/// # Arguments
+/// * `stream_id`: stream of the frame.
+///
+/// # Returns
+/// [`Frame::Priority`] and its encoding.
+pub fn priority_frame(stream_id: u32) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  priority(stream_id).prop_map(move |priority| {
+    let frame = Frame::Priority { stream_id, priority };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating error code.
+pub fn error_code() -> impl Strategy<Value = ErrorCode> {
+  proptest::sample::select(&[
+    ErrorCode::NoError,
+    ErrorCode::ProtocolError,
+    ErrorCode::InternalError,
+    ErrorCode::FlowControlError,
+    ErrorCode::SettingsTimeout,
+    ErrorCode::StreamClosed,
+    ErrorCode::FrameSizeError,
+    ErrorCode::RefusedStream,
+    ErrorCode::Cancel,
+    ErrorCode::CompressionError,
+    ErrorCode::ConnectError,
+    ErrorCode::EnhanceYourCalm,
+    ErrorCode::InadequateSecurity,
+    ErrorCode::Http11Required,
+  ])
+}
+
+/// strategy for generating RST_STREAM frame.
+///
+/// # Arguments
+/// * `stream_id`: stream of the frame.
+///
+/// # Returns
+/// [`Frame::RstStream`] and its encoding.
+pub fn rst_stream_frame(stream_id: u32) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  error_code().prop_map(move |error_code| {
+    let frame = Frame::RstStream { stream_id, error_code };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating SETTINGS frame.
+///
+/// acknowledgments have no parameters.
+///
+/// # Returns
+/// [`Frame::Settings`] and its encoding.
+pub fn settings_frame() -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  prop_oneof![
+    3 => vec(setting(), 0..=8).prop_map(|settings| Frame::Settings { ack: false, settings }),
+    1 => Just(Frame::Settings { ack: true, settings: vec![] }),
+  ]
+  .prop_map(|frame| {
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating PING frame.
+///
+/// # Returns
+/// [`Frame::Ping`] and its encoding.
+pub fn ping_frame() -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (any::<bool>(), any::<[u8; 8]>()).prop_map(|(ack, data)| {
+    let frame = Frame::Ping { ack, data };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating GOAWAY frame.
+///
+/// # Returns
+/// [`Frame::GoAway`] and its encoding.
+pub fn goaway_frame() -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (0u32..(1 << 31), error_code(), vec(any::<u8>(), 0..=64)).prop_map(
+    |(last_stream_id, error_code, debug_data)| {
+      let frame = Frame::GoAway { last_stream_id, error_code, debug_data };
+      let repr = frame.to_bytes();
+      (frame, repr)
+    },
+  )
+}
+
+/// strategy for generating WINDOW_UPDATE frame.
+///
+/// # Arguments
+/// * `stream_id`: stream of the frame, `0` for the connection window.
+///
+/// # Returns
+/// [`Frame::WindowUpdate`] and its encoding.
+pub fn window_update_frame(stream_id: u32) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (1..=MAX_WINDOW_SIZE).prop_map(move |increment| {
+    let frame = Frame::WindowUpdate { stream_id, increment };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  /// parsed frame header and payload.
+  #[derive(Debug)]
+  pub(crate) struct RawFrame<'a> {
+    pub(crate) frame_type: u8,
+    pub(crate) flags: u8,
+    pub(crate) stream_id: u32,
+    pub(crate) payload: &'a [u8],
+  }
+
+  /// split octets into frames.
+  pub(crate) fn parse_frames(mut input: &[u8]) -> Vec<RawFrame<'_>> {
+    let mut frames = vec![];
+    while !input.is_empty() {
+      assert!(input.len() >= FRAME_HEADER_LEN, "truncated frame header {input:?}");
+      let length = u32::from_be_bytes([0, input[0], input[1], input[2]]) as usize;
+      let stream_id = u32::from_be_bytes(input[5..9].try_into().unwrap());
+      assert_eq!(stream_id >> 31, 0, "reserved bit should not be set");
+      let payload = &input[FRAME_HEADER_LEN..FRAME_HEADER_LEN + length];
+      frames.push(RawFrame { frame_type: input[3], flags: input[4], stream_id, payload });
+      input = &input[FRAME_HEADER_LEN + length..];
+    }
+    frames
+  }
+
+  /// strip padding and priority from DATA or HEADERS payload.
+  pub(crate) fn unpadded<'a>(frame: &RawFrame<'a>) -> &'a [u8] {
+    let mut payload = frame.payload;
+    if frame.flags & PADDED_FLAG != 0 {
+      let padding = usize::from(payload[0]);
+      payload = &payload[1..payload.len() - padding];
+    }
+    if frame.frame_type == HEADERS && frame.flags & PRIORITY_FLAG != 0 {
+      payload = &payload[5..];
+    }
+    payload
+  }
+
+  fn frame_asserts(frame: &Frame, repr: &[u8]) {
+    let raw_frames = parse_frames(repr);
+    assert_eq!(raw_frames.len(), 1, "expected a single frame in {repr:?}");
+    let raw_frame = &raw_frames[0];
+    assert_eq!(raw_frame.stream_id, frame.stream_id());
+
+    match frame {
+      Frame::Data { end_stream, data, .. } => {
+        assert_eq!(raw_frame.frame_type, DATA);
+        assert_eq!(raw_frame.flags & END_STREAM_FLAG != 0, *end_stream);
+        assert_eq!(unpadded(raw_frame), data);
+      }
+      Frame::Headers { end_stream, fragment, .. } => {
+        assert_eq!(raw_frame.frame_type, HEADERS);
+        assert_eq!(raw_frame.flags & END_STREAM_FLAG != 0, *end_stream);
+        assert_ne!(raw_frame.flags & END_HEADERS_FLAG, 0);
+        assert_eq!(unpadded(raw_frame), fragment);
+        let mut decoder = loona_hpack::Decoder::new();
+        assert!(decoder.decode(fragment).is_ok(), "invalid header block {fragment:?}");
+      }
+      Frame::Priority { .. } => {
+        assert_eq!(raw_frame.frame_type, PRIORITY);
+        assert_eq!(raw_frame.payload.len(), 5);
+      }
+      Frame::RstStream { error_code, .. } => {
+        assert_eq!(raw_frame.frame_type, RST_STREAM);
+        assert_eq!(raw_frame.payload, (*error_code as u32).to_be_bytes());
+      }
+      Frame::Settings { ack, settings } => {
+        assert_eq!(raw_frame.frame_type, SETTINGS);
+        assert_eq!(raw_frame.flags & ACK_FLAG != 0, *ack);
+        assert_eq!(raw_frame.payload.len(), 6 * settings.len());
+        if *ack {
+          assert!(settings.is_empty(), "SETTINGS acknowledgment should be empty");
+        }
+      }
+      Frame::Ping { data, .. } => {
+        assert_eq!(raw_frame.frame_type, PING);
+        assert_eq!(raw_frame.payload, data);
+      }
+      Frame::GoAway { debug_data, .. } => {
+        assert_eq!(raw_frame.frame_type, GOAWAY);
+        assert_eq!(&raw_frame.payload[8..], debug_data);
+      }
+      Frame::WindowUpdate { increment, .. } => {
+        assert_eq!(raw_frame.frame_type, WINDOW_UPDATE);
+        assert!((1..=MAX_WINDOW_SIZE).contains(increment));
+        assert_eq!(raw_frame.payload, increment.to_be_bytes());
+      }
+      Frame::Continuation { fragment, .. } => {
+        assert_eq!(raw_frame.frame_type, CONTINUATION);
+        assert_eq!(raw_frame.payload, fragment);
+      }
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn frames_works(
+      (frame, repr) in client_stream_id().prop_flat_map(|stream_id| prop_oneof![
+        data_frame(stream_id, 0..=1024),
+        headers_frame(stream_id, 0..=10),
+        continuation_frame(stream_id),
+        priority_frame(stream_id),
+        rst_stream_frame(stream_id),
+        settings_frame(),
+        ping_frame(),
+        goaway_frame(),
+        window_update_frame(stream_id),
+      ])
+    ) {
+      frame_asserts(&frame, &repr);
+    }
+  }
+}
@@ -0,0 +1,467 @@
+//! HPACK (RFC 7541) header block strategies.
+//!
+//! header lists are encoded with randomly chosen representations: indexed fields, literals with
+//! incremental indexing, without indexing or never indexed, Huffman or raw string literals, and
+//! dynamic table size updates. The decoded header list is the model.
+
+use std::{collections::VecDeque, ops::RangeInclusive};
+
+use proptest::{
+  bool::weighted,
+  collection::vec,
+  prelude::{Just, Strategy},
+  prop_oneof,
+  sample::select,
+};
+
+use crate::header::{HeaderField, field_value, header_field};
+
+pub(crate) mod huffman;
+
+/// default `SETTINGS_HEADER_TABLE_SIZE` and maximum dynamic table size.
+pub const DEFAULT_HEADER_TABLE_SIZE: usize = 4096;
+
+/// overhead added to the length of name and value to get the size of a dynamic table entry.
+const ENTRY_OVERHEAD: usize = 32;
+
+/// HPACK static table (RFC 7541 appendix A), the index of an entry is its position plus one.
+pub(crate) const STATIC_TABLE: [(&str, &str); 61] = [
+  (":authority", ""),
+  (":method", "GET"),
+  (":method", "POST"),
+  (":path", "/"),
+  (":path", "/index.html"),
+  (":scheme", "http"),
+  (":scheme", "https"),
+  (":status", "200"),
+  (":status", "204"),
+  (":status", "206"),
+  (":status", "304"),
+  (":status", "400"),
+  (":status", "404"),
+  (":status", "500"),
+  ("accept-charset", ""),
+  ("accept-encoding", "gzip, deflate"),
+  ("accept-language", ""),
+  ("accept-ranges", ""),
+  ("accept", ""),
+  ("access-control-allow-origin", ""),
+  ("age", ""),
+  ("allow", ""),
+  ("authorization", ""),
+  ("cache-control", ""),
+  ("content-disposition", ""),
+  ("content-encoding", ""),
+  ("content-language", ""),
+  ("content-length", ""),
+  ("content-location", ""),
+  ("content-range", ""),
+  ("content-type", ""),
+  ("cookie", ""),
+  ("date", ""),
+  ("etag", ""),
+  ("expect", ""),
+  ("expires", ""),
+  ("from", ""),
+  ("host", ""),
+  ("if-match", ""),
+  ("if-modified-since", ""),
+  ("if-none-match", ""),
+  ("if-range", ""),
+  ("if-unmodified-since", ""),
+  ("last-modified", ""),
+  ("link", ""),
+  ("location", ""),
+  ("max-forwards", ""),
+  ("proxy-authenticate", ""),
+  ("proxy-authorization", ""),
+  ("range", ""),
+  ("referer", ""),
+  ("refresh", ""),
+  ("retry-after", ""),
+  ("server", ""),
+  ("set-cookie", ""),
+  ("strict-transport-security", ""),
+  ("transfer-encoding", ""),
+  ("user-agent", ""),
+  ("vary", ""),
+  ("via", ""),
+  ("www-authenticate", ""),
+];
+
+/// kind of literal header field representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralKind {
+  /// field is added to the dynamic table.
+  WithIndexing,
+  /// field is not added to the dynamic table.
+  WithoutIndexing,
+  /// field is not added to the dynamic table and intermediaries must not index it either.
+  NeverIndexed,
+}
+
+/// HPACK header field representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Representation {
+  /// field is fully referenced from the static or dynamic table.
+  Indexed(usize),
+  /// field is a literal, its name may be referenced from the static or dynamic table.
+  Literal { kind: LiteralKind, name_index: Option<usize>, huffman_name: bool, huffman_value: bool },
+  /// dynamic table size update to the given maximum size.
+  SizeUpdate(usize),
+}
+
+/// HPACK header block.
+#[derive(Debug, Clone)]
+pub struct HeaderBlock {
+  /// decoded header list.
+  pub fields: Vec<HeaderField>,
+  /// representations used to encode the block, in order.
+  pub representations: Vec<Representation>,
+}
+
+/// random encoding decisions for a single header field.
+#[derive(Debug, Clone)]
+struct FieldChoice {
+  indexed: bool,
+  kind: LiteralKind,
+  name_index: bool,
+  huffman_name: bool,
+  huffman_value: bool,
+}
+
+fn field_choice() -> impl Strategy<Value = FieldChoice> {
+  (
+    weighted(0.7),
+    select(&[LiteralKind::WithIndexing, LiteralKind::WithoutIndexing, LiteralKind::NeverIndexed]),
+    weighted(0.7),
+    weighted(0.5),
+    weighted(0.5),
+  )
+    .prop_map(|(indexed, kind, name_index, huffman_name, huffman_value)| FieldChoice {
+      indexed,
+      kind,
+      name_index,
+      huffman_name,
+      huffman_value,
+    })
+}
+
+/// encode integer with a `prefix_bits` bits prefix (RFC 7541 section 5.1).
+///
+/// `flags` are the bits of the first octet that are not part of the prefix.
+pub(crate) fn encode_integer(value: usize, prefix_bits: u8, flags: u8, output: &mut Vec<u8>) {
+  let max_prefix = (1usize << prefix_bits) - 1;
+  if value < max_prefix {
+    output.push(flags | value as u8);
+    return;
+  }
+
+  output.push(flags | max_prefix as u8);
+  let mut value = value - max_prefix;
+  while value >= 0x80 {
+    output.push((value & 0x7f) as u8 | 0x80);
+    value >>= 7;
+  }
+  output.push(value as u8);
+}
+
+/// encode string literal with its length as a `prefix_bits` bits prefix integer (RFC 7541
+/// section 5.2).
+///
+/// the Huffman flag is the bit right before the prefix.
+pub(crate) fn encode_string(
+  input: &[u8],
+  huffman: bool,
+  prefix_bits: u8,
+  flags: u8,
+  output: &mut Vec<u8>,
+) {
+  if huffman {
+    let encoded = huffman::huffman_encode(input);
+    encode_integer(encoded.len(), prefix_bits, flags | (1 << prefix_bits), output);
+    output.extend_from_slice(&encoded);
+  } else {
+    encode_integer(input.len(), prefix_bits, flags, output);
+    output.extend_from_slice(input);
+  }
+}
+
+/// size of a dynamic table entry.
+fn entry_size(field: &HeaderField) -> usize {
+  field.name.len() + field.value.len() + ENTRY_OVERHEAD
+}
+
+/// HPACK encoder state shared by all the header blocks of a connection.
+struct Encoder {
+  /// dynamic table, newest entry first.
+  dynamic_table: VecDeque<HeaderField>,
+  size: usize,
+  max_size: usize,
+}
+
+impl Encoder {
+  fn new(max_size: usize) -> Self {
+    Self { dynamic_table: VecDeque::new(), size: 0, max_size }
+  }
+
+  fn evict(&mut self) {
+    while self.size > self.max_size {
+      let field = self.dynamic_table.pop_back().expect("non empty dynamic table");
+      self.size -= entry_size(&field);
+    }
+  }
+
+  fn set_max_size(&mut self, max_size: usize) {
+    self.max_size = max_size;
+    self.evict();
+  }
+
+  fn insert(&mut self, field: &HeaderField) {
+    self.size += entry_size(field);
+    self.dynamic_table.push_front(field.clone());
+    // an entry larger than the table empties it.
+    self.evict();
+  }
+
+  /// find field in static and dynamic tables.
+  ///
+  /// # Returns
+  /// index of the whole field, and index of the field name.
+  fn find(&self, field: &HeaderField) -> (Option<usize>, Option<usize>) {
+    let entries = STATIC_TABLE
+      .iter()
+      .map(|(name, value)| (*name, *value))
+      .chain(self.dynamic_table.iter().map(|field| (field.name.as_str(), field.value.as_str())));
+
+    let mut name_index = None;
+    for (index, (name, value)) in (1..).zip(entries) {
+      if name == field.name {
+        if value == field.value {
+          return (Some(index), Some(index));
+        }
+        name_index = name_index.or(Some(index));
+      }
+    }
+    (None, name_index)
+  }
+
+  fn encode(
+    &mut self,
+    fields: &[HeaderField],
+    choices: &[FieldChoice],
+    size_updates: &[usize],
+  ) -> (Vec<Representation>, Vec<u8>) {
+    let mut representations = Vec::with_capacity(fields.len() + size_updates.len());
+    let mut output = Vec::new();
+
+    // size updates are only allowed at the beginning of a non empty block.
+    if !fields.is_empty() {
+      for size in size_updates {
+        self.set_max_size(*size);
+        encode_integer(*size, 5, 0x20, &mut output);
+        representations.push(Representation::SizeUpdate(*size));
+      }
+    }
+
+    for (field, choice) in fields.iter().zip(choices) {
+      let (index, name_index) = self.find(field);
+      if choice.indexed
+        && let Some(index) = index
+      {
+        encode_integer(index, 7, 0x80, &mut output);
+        representations.push(Representation::Indexed(index));
+        continue;
+      }
+
+      let name_index = name_index.filter(|_| choice.name_index);
+      let (prefix_bits, flags) = match choice.kind {
+        LiteralKind::WithIndexing => (6, 0x40),
+        LiteralKind::WithoutIndexing => (4, 0x00),
+        LiteralKind::NeverIndexed => (4, 0x10),
+      };
+      encode_integer(name_index.unwrap_or_default(), prefix_bits, flags, &mut output);
+      if name_index.is_none() {
+        encode_string(field.name.as_bytes(), choice.huffman_name, 7, 0, &mut output);
+      }
+      encode_string(field.value.as_bytes(), choice.huffman_value, 7, 0, &mut output);
+      if choice.kind == LiteralKind::WithIndexing {
+        self.insert(field);
+      }
+
+      representations.push(Representation::Literal {
+        kind: choice.kind,
+        name_index,
+        huffman_name: name_index.is_none() && choice.huffman_name,
+        huffman_value: choice.huffman_value,
+      });
+    }
+
+    (representations, output)
+  }
+}
+
+/// strategy for generating dynamic table size updates of a header block.
+fn size_updates() -> impl Strategy<Value = Vec<usize>> {
+  prop_oneof![
+    4 => Just(vec![]),
+    1 => vec(0..=DEFAULT_HEADER_TABLE_SIZE, 1..=2),
+  ]
+}
+
+/// strategy for generating header list likely to hit the given static table and the dynamic
+/// table.
+///
+/// fields are picked from a small pool of static table entries, static table names with random
+/// values and random fields, so fields repeat within and across lists. Pseudo header fields are
+/// left out.
+pub(crate) fn header_list_with(
+  static_table: &'static [(&'static str, &'static str)],
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = Vec<HeaderField>> {
+  let static_entries =
+    static_table.iter().filter(|(name, _)| !name.starts_with(':')).copied().collect::<Vec<_>>();
+  let field = prop_oneof![
+    select(static_entries.clone())
+      .prop_map(|(name, value)| HeaderField { name: name.to_string(), value: value.to_string() }),
+    (select(static_entries), field_value())
+      .prop_map(|((name, _), value)| HeaderField { name: name.to_string(), value }),
+    header_field().prop_map(|(field, _)| field),
+  ];
+
+  vec(field, 1..=8).prop_flat_map(move |pool| vec(select(pool), count_range.clone()))
+}
+
+/// strategy for generating header list likely to hit the static and dynamic tables.
+///
+/// fields are picked from a small pool of static table entries, static table names with random
+/// values and random fields, so fields repeat within and across lists.
+///
+/// # Arguments
+/// * `count_range`: range of the number of header fields.
+pub fn header_list(count_range: RangeInclusive<usize>) -> impl Strategy<Value = Vec<HeaderField>> {
+  header_list_with(&STATIC_TABLE, count_range)
+}
+
+/// strategy for encoding header lists as HPACK header blocks of the same connection.
+///
+/// the blocks share the same dynamic table, they must be decoded in order.
+///
+/// # Arguments
+/// * `lists`: header lists to encode.
+///
+/// # Returns
+/// [`HeaderBlock`] and its encoding for every header list.
+pub fn encode_header_lists(
+  lists: Vec<Vec<HeaderField>>,
+) -> impl Strategy<Value = (Vec<HeaderBlock>, Vec<Vec<u8>>)> {
+  let choices =
+    lists.iter().map(|list| (size_updates(), vec(field_choice(), list.len()))).collect::<Vec<_>>();
+
+  (Just(lists), choices).prop_map(|(lists, choices)| {
+    let mut encoder = Encoder::new(DEFAULT_HEADER_TABLE_SIZE);
+    lists
+      .into_iter()
+      .zip(choices)
+      .map(|(fields, (size_updates, choices))| {
+        let (representations, repr) = encoder.encode(&fields, &choices, &size_updates);
+        (HeaderBlock { fields, representations }, repr)
+      })
+      .unzip()
+  })
+}
+
+/// strategy for generating HPACK header block.
+///
+/// # Arguments
+/// * `count_range`: range of the number of header fields.
+///
+/// # Returns
+/// [`HeaderBlock`] and its encoding.
+pub fn header_block(
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (HeaderBlock, Vec<u8>)> {
+  header_list(count_range).prop_flat_map(|list| {
+    encode_header_lists(vec![list]).prop_map(|(mut blocks, mut reprs)| {
+      (blocks.pop().expect("one block"), reprs.pop().expect("one block"))
+    })
+  })
+}
+
+/// strategy for generating HPACK header blocks of the same connection.
+///
+/// see [`encode_header_lists`].
+///
+/// # Arguments
+/// * `block_count_range`: range of the number of header blocks.
+/// * `count_range`: range of the number of header fields per block.
+///
+/// # Returns
+/// [`HeaderBlock`] and its encoding for every block.
+pub fn header_blocks(
+  block_count_range: RangeInclusive<usize>,
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Vec<HeaderBlock>, Vec<Vec<u8>>)> {
+  vec(header_list(count_range), block_count_range).prop_flat_map(encode_header_lists)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use loona_hpack::Decoder;
+  use proptest::proptest;
+
+  use super::*;
+
+  pub(crate) fn decoded_fields(decoded: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<HeaderField> {
+    decoded
+      .into_iter()
+      .map(|(name, value)| HeaderField {
+        name: String::from_utf8(name).unwrap(),
+        value: String::from_utf8(value).unwrap(),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn encode_integer_works() {
+    // RFC 7541 appendix C.1
+    let mut output = vec![];
+    encode_integer(10, 5, 0, &mut output);
+    assert_eq!(output, [0x0a]);
+
+    output.clear();
+    encode_integer(1337, 5, 0, &mut output);
+    assert_eq!(output, [0x1f, 0x9a, 0x0a]);
+
+    output.clear();
+    encode_integer(42, 8, 0, &mut output);
+    assert_eq!(output, [0x2a]);
+  }
+
+  proptest! {
+    #[test]
+    fn header_block_works((block, repr) in header_block(0..=20)) {
+      let mut decoder = Decoder::new();
+      decoder.set_max_allowed_table_size(DEFAULT_HEADER_TABLE_SIZE);
+      let decoded = decoder.decode(&repr).unwrap_or_else(|e| panic!("failed to decode {repr:?}: {e:?}"));
+      assert_eq!(decoded_fields(decoded), block.fields);
+
+      let field_representations = block
+        .representations
+        .iter()
+        .filter(|representation| !matches!(representation, Representation::SizeUpdate(_)))
+        .count();
+      assert_eq!(field_representations, block.fields.len());
+    }
+
+    #[test]
+    fn header_blocks_works((blocks, reprs) in header_blocks(1..=5, 0..=20)) {
+      let mut decoder = Decoder::new();
+      decoder.set_max_allowed_table_size(DEFAULT_HEADER_TABLE_SIZE);
+      for (block, repr) in blocks.iter().zip(reprs) {
+        let decoded = decoder.decode(&repr).unwrap_or_else(|e| panic!("failed to decode {repr:?}: {e:?}"));
+        assert_eq!(decoded_fields(decoded), block.fields);
+      }
+    }
+  }
+}
@@ -0,0 +1,6 @@
+//! HTTP/2 strategies.
+
+pub mod connection;
+pub mod frame;
+pub mod hpack;
+pub mod violation;
@@ -0,0 +1,491 @@
+//! HTTP/2 protocol violation strategies.
+//!
+//! every violation is labelled with the error code and the scope (connection or stream) of the
+//! error a conforming server reports, with GOAWAY or RST_STREAM respectively.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+  sample::select,
+};
+
+use crate::{
+  h2::{
+    connection::{CONNECTION_PREFACE, CONNECTION_SPECIFIC_HEADERS, request_headers},
+    frame::{
+      DEFAULT_MAX_FRAME_SIZE, DEFAULT_WINDOW_SIZE, ErrorCode, Frame, MAX_WINDOW_SIZE, Setting,
+      SettingId,
+    },
+    hpack::encode_header_lists,
+  },
+  header::{HeaderField, field_name, field_value},
+  request_line::{pseudo_headers::pseudo_headers, version::HttpVersion},
+};
+
+/// stream carrying the request of stream level violations.
+const STREAM_ID: u32 = 1;
+
+/// HTTP/2 protocol violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+  /// DATA frame on stream `0`.
+  DataOnStreamZero,
+  /// HEADERS frame opening an even, server initiated, stream.
+  HeadersOnEvenStream,
+  /// header block interrupted by another frame before END_HEADERS.
+  InterleavedContinuation,
+  /// frame larger than `SETTINGS_MAX_FRAME_SIZE`.
+  OversizedFrame,
+  /// WINDOW_UPDATE of the connection window beyond 2^31-1.
+  ConnectionWindowOverflow,
+  /// WINDOW_UPDATE of a stream window beyond 2^31-1.
+  StreamWindowOverflow,
+  /// WINDOW_UPDATE with a `0` increment on the connection.
+  ZeroWindowIncrement,
+  /// DATA beyond the connection flow control window.
+  DataExceedingWindow,
+  /// SETTINGS parameter with an invalid value.
+  InvalidSetting,
+  /// header field name with upper case characters.
+  UppercaseHeaderName,
+  /// connection-specific header field, or `te` with a value other than `trailers`.
+  ConnectionSpecificHeader,
+  /// pseudo header field after a regular header field.
+  PseudoHeaderAfterRegular,
+}
+
+/// scope of the error reported by the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorScope {
+  /// connection error, reported with GOAWAY.
+  Connection,
+  /// stream error, reported with RST_STREAM on the given stream.
+  Stream(u32),
+}
+
+/// HTTP/2 protocol violation and the expected error.
+#[derive(Debug, Clone)]
+pub struct Violation {
+  pub kind: ViolationKind,
+  pub error_code: ErrorCode,
+  pub scope: ErrorScope,
+  /// frames following the preface, the client SETTINGS frame included.
+  pub frames: Vec<Frame>,
+}
+
+impl Violation {
+  fn connection(kind: ViolationKind, error_code: ErrorCode, frames: Vec<Frame>) -> Self {
+    Self { kind, error_code, scope: ErrorScope::Connection, frames }
+  }
+
+  fn stream(kind: ViolationKind, error_code: ErrorCode, frames: Vec<Frame>) -> Self {
+    Self { kind, error_code, scope: ErrorScope::Stream(STREAM_ID), frames }
+  }
+}
+
+/// strategy for generating valid request header list.
+fn request_list(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = Vec<HeaderField>> {
+  (pseudo_headers(max_label_count, max_segments, query_count_range), request_headers()).prop_map(
+    |((mut pseudo_headers, _), headers)| {
+      pseudo_headers.version = HttpVersion::Http2;
+      pseudo_headers
+        .fields()
+        .into_iter()
+        .map(|(name, value)| HeaderField { name: name.to_string(), value })
+        .chain(headers)
+        .collect()
+    },
+  )
+}
+
+/// strategy for encoding a header list as a single HPACK header block.
+fn header_block(list: Vec<HeaderField>) -> impl Strategy<Value = Vec<u8>> {
+  encode_header_lists(vec![list]).prop_map(|(_, mut blocks)| blocks.pop().expect("one block"))
+}
+
+/// HEADERS frame carrying a whole header block.
+fn headers(stream_id: u32, end_stream: bool, fragment: Vec<u8>) -> Frame {
+  Frame::Headers {
+    stream_id,
+    end_stream,
+    end_headers: true,
+    padding: None,
+    priority: None,
+    fragment,
+  }
+}
+
+fn data_on_stream_zero() -> impl Strategy<Value = Violation> {
+  vec(any::<u8>(), 0..=64).prop_map(|data| {
+    Violation::connection(
+      ViolationKind::DataOnStreamZero,
+      ErrorCode::ProtocolError,
+      vec![Frame::Data { stream_id: 0, end_stream: false, padding: None, data }],
+    )
+  })
+}
+
+fn headers_on_even_stream(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  (list.prop_flat_map(header_block), (1u32..(1 << 30)).prop_map(|n| 2 * n)).prop_map(
+    |(block, stream_id)| {
+      Violation::connection(
+        ViolationKind::HeadersOnEvenStream,
+        ErrorCode::ProtocolError,
+        vec![headers(stream_id, true, block)],
+      )
+    },
+  )
+}
+
+fn interleaved_continuation(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  let interruption = prop_oneof![
+    any::<[u8; 8]>().prop_map(|data| Frame::Ping { ack: false, data }),
+    vec(any::<u8>(), 0..=16).prop_map(|data| Frame::Data {
+      stream_id: STREAM_ID,
+      end_stream: false,
+      padding: None,
+      data
+    }),
+    (1..=u32::from(u16::MAX)).prop_map(|increment| Frame::WindowUpdate { stream_id: 0, increment }),
+    Just(Frame::Headers {
+      stream_id: STREAM_ID + 2,
+      end_stream: true,
+      end_headers: true,
+      padding: None,
+      priority: None,
+      fragment: vec![],
+    }),
+  ];
+
+  (list.prop_flat_map(header_block), any::<proptest::sample::Index>(), interruption).prop_map(
+    |(block, split, interruption)| {
+      let (first, rest) = block.split_at(split.index(block.len() + 1));
+      Violation::connection(
+        ViolationKind::InterleavedContinuation,
+        ErrorCode::ProtocolError,
+        vec![
+          Frame::Headers {
+            stream_id: STREAM_ID,
+            end_stream: true,
+            end_headers: false,
+            padding: None,
+            priority: None,
+            fragment: first.to_vec(),
+          },
+          interruption,
+          Frame::Continuation { stream_id: STREAM_ID, end_headers: true, fragment: rest.to_vec() },
+        ],
+      )
+    },
+  )
+}
+
+fn oversized_frame(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  // even Huffman encoded, the value alone does not fit in a frame.
+  (list, 3 * DEFAULT_MAX_FRAME_SIZE / 2..=2 * DEFAULT_MAX_FRAME_SIZE)
+    .prop_flat_map(|(mut list, len)| {
+      list.push(HeaderField { name: "x-large".to_string(), value: "x".repeat(len) });
+      header_block(list)
+    })
+    .prop_map(|block| {
+      Violation::connection(
+        ViolationKind::OversizedFrame,
+        ErrorCode::FrameSizeError,
+        vec![headers(STREAM_ID, true, block)],
+      )
+    })
+}
+
+/// strategy for generating window increment overflowing the initial window.
+fn overflowing_increment() -> impl Strategy<Value = u32> {
+  MAX_WINDOW_SIZE - DEFAULT_WINDOW_SIZE + 1..=MAX_WINDOW_SIZE
+}
+
+fn connection_window_overflow() -> impl Strategy<Value = Violation> {
+  overflowing_increment().prop_map(|increment| {
+    Violation::connection(
+      ViolationKind::ConnectionWindowOverflow,
+      ErrorCode::FlowControlError,
+      vec![Frame::WindowUpdate { stream_id: 0, increment }],
+    )
+  })
+}
+
+fn stream_window_overflow(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  (list.prop_flat_map(header_block), overflowing_increment()).prop_map(|(block, increment)| {
+    Violation::stream(
+      ViolationKind::StreamWindowOverflow,
+      ErrorCode::FlowControlError,
+      vec![
+        headers(STREAM_ID, true, block),
+        Frame::WindowUpdate { stream_id: STREAM_ID, increment },
+      ],
+    )
+  })
+}
+
+fn zero_window_increment() -> impl Strategy<Value = Violation> {
+  Just(Violation::connection(
+    ViolationKind::ZeroWindowIncrement,
+    ErrorCode::ProtocolError,
+    vec![Frame::WindowUpdate { stream_id: 0, increment: 0 }],
+  ))
+}
+
+fn data_exceeding_window(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  (list.prop_flat_map(header_block), any::<u8>(), 1..=DEFAULT_MAX_FRAME_SIZE).prop_map(
+    |(block, octet, extra)| {
+      let mut frames = vec![headers(STREAM_ID, false, block)];
+      let mut remaining = DEFAULT_WINDOW_SIZE as usize + extra;
+      while remaining > 0 {
+        let len = remaining.min(DEFAULT_MAX_FRAME_SIZE);
+        remaining -= len;
+        frames.push(Frame::Data {
+          stream_id: STREAM_ID,
+          end_stream: remaining == 0,
+          padding: None,
+          data: vec![octet; len],
+        });
+      }
+      Violation::connection(ViolationKind::DataExceedingWindow, ErrorCode::FlowControlError, frames)
+    },
+  )
+}
+
+fn invalid_setting() -> impl Strategy<Value = Violation> {
+  prop_oneof![
+    (2..=u32::MAX).prop_map(|value| {
+      (Setting { id: SettingId::EnablePush, value }, ErrorCode::ProtocolError)
+    }),
+    (MAX_WINDOW_SIZE + 1..=u32::MAX).prop_map(|value| {
+      (Setting { id: SettingId::InitialWindowSize, value }, ErrorCode::FlowControlError)
+    }),
+    prop_oneof![0..DEFAULT_MAX_FRAME_SIZE as u32, 1 << 24..=u32::MAX].prop_map(|value| {
+      (Setting { id: SettingId::MaxFrameSize, value }, ErrorCode::ProtocolError)
+    }),
+    (2..=u32::MAX).prop_map(|value| {
+      (Setting { id: SettingId::EnableConnectProtocol, value }, ErrorCode::ProtocolError)
+    }),
+  ]
+  .prop_map(|(setting, error_code)| {
+    Violation::connection(
+      ViolationKind::InvalidSetting,
+      error_code,
+      vec![Frame::Settings { ack: false, settings: vec![setting] }],
+    )
+  })
+}
+
+/// malformed request, reported as a stream error.
+fn malformed_request(
+  kind: ViolationKind,
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  list.prop_flat_map(header_block).prop_map(move |block| {
+    Violation::stream(kind, ErrorCode::ProtocolError, vec![headers(STREAM_ID, true, block)])
+  })
+}
+
+fn uppercase_header_name(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  let field = (field_name(), field_value(), any::<proptest::sample::Index>()).prop_map(
+    |(name, value, index)| {
+      let letters = name.match_indices(|c: char| c.is_ascii_lowercase()).collect::<Vec<_>>();
+      let name = if letters.is_empty() {
+        format!("X{name}")
+      } else {
+        let (position, _) = letters[index.index(letters.len())];
+        let mut name = name.into_bytes();
+        name[position].make_ascii_uppercase();
+        String::from_utf8(name).expect("ASCII name")
+      };
+      HeaderField { name, value }
+    },
+  );
+
+  malformed_request(
+    ViolationKind::UppercaseHeaderName,
+    (list, field).prop_map(|(mut list, field)| {
+      list.push(field);
+      list
+    }),
+  )
+}
+
+fn connection_specific_header(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  let field = prop_oneof![
+    (select(&CONNECTION_SPECIFIC_HEADERS[..]), field_value())
+      .prop_map(|(name, value)| HeaderField { name: name.to_string(), value }),
+    field_value()
+      .prop_filter("trailers te", |value| value != "trailers")
+      .prop_map(|value| HeaderField { name: "te".to_string(), value }),
+  ];
+
+  malformed_request(
+    ViolationKind::ConnectionSpecificHeader,
+    (list, field).prop_map(|(mut list, field)| {
+      list.push(field);
+      list
+    }),
+  )
+}
+
+fn pseudo_header_after_regular(
+  list: impl Strategy<Value = Vec<HeaderField>>,
+) -> impl Strategy<Value = Violation> {
+  malformed_request(
+    ViolationKind::PseudoHeaderAfterRegular,
+    (list, field_name(), field_value()).prop_map(|(mut list, name, value)| {
+      // move the last pseudo header field after a regular one.
+      let pseudo = list.iter().rposition(|field| field.name.starts_with(':')).expect("pseudo");
+      let pseudo = list.remove(pseudo);
+      list.push(HeaderField { name, value });
+      list.push(pseudo);
+      list
+    }),
+  )
+}
+
+/// strategy for generating HTTP/2 protocol violation.
+///
+/// the connection starts with the client preface and an empty SETTINGS frame, unless the
+/// violation is an invalid SETTINGS parameter, followed by the frames violating the protocol.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`Violation`] and the connection bytes.
+pub fn violation(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Violation, Vec<u8>)> {
+  // boxed, the request list strategy is large and shared by most of the violations.
+  let list = move || request_list(max_label_count, max_segments, query_count_range.clone()).boxed();
+
+  prop_oneof![
+    data_on_stream_zero(),
+    headers_on_even_stream(list()),
+    interleaved_continuation(list()),
+    oversized_frame(list()),
+    connection_window_overflow(),
+    stream_window_overflow(list()),
+    zero_window_increment(),
+    data_exceeding_window(list()),
+    invalid_setting(),
+    uppercase_header_name(list()),
+    connection_specific_header(list()),
+    pseudo_header_after_regular(list()),
+  ]
+  .prop_map(|mut violation| {
+    if violation.kind != ViolationKind::InvalidSetting {
+      violation.frames.insert(0, Frame::Settings { ack: false, settings: vec![] });
+    }
+
+    let mut repr = CONNECTION_PREFACE.to_vec();
+    for frame in &violation.frames {
+      frame.encode(&mut repr);
+    }
+    (violation, repr)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use loona_hpack::Decoder;
+  use proptest::proptest;
+
+  use super::*;
+  use crate::h2::{frame::tests::parse_frames, hpack::tests::decoded_fields};
+
+  proptest! {
+    #[test]
+    fn violation_works((violation, repr) in violation(20, 50.try_into().unwrap(), 0..=10)) {
+      assert!(repr.starts_with(CONNECTION_PREFACE));
+      let frames = parse_frames(&repr[CONNECTION_PREFACE.len()..]);
+      assert_eq!(frames.len(), violation.frames.len());
+      assert_eq!(frames[0].frame_type, 0x4, "first frame should be SETTINGS");
+
+      let header_fields = || {
+        let headers = frames.iter().find(|frame| frame.frame_type == 0x1).unwrap();
+        decoded_fields(Decoder::new().decode(headers.payload).unwrap())
+      };
+
+      match violation.kind {
+        ViolationKind::DataOnStreamZero => {
+          assert!(frames.iter().any(|frame| frame.frame_type == 0x0 && frame.stream_id == 0));
+        }
+        ViolationKind::HeadersOnEvenStream => {
+          assert_eq!(frames[1].frame_type, 0x1);
+          assert_eq!(frames[1].stream_id % 2, 0);
+        }
+        ViolationKind::InterleavedContinuation => {
+          assert_eq!(frames[1].flags & 0x4, 0, "HEADERS should not end headers");
+          assert_ne!(frames[2].frame_type, 0x9);
+          assert_eq!(frames[3].frame_type, 0x9);
+        }
+        ViolationKind::OversizedFrame => {
+          assert!(frames.iter().any(|frame| frame.payload.len() > DEFAULT_MAX_FRAME_SIZE));
+        }
+        ViolationKind::ConnectionWindowOverflow | ViolationKind::StreamWindowOverflow => {
+          let window_update = frames.iter().find(|frame| frame.frame_type == 0x8).unwrap();
+          let increment = u32::from_be_bytes(window_update.payload.try_into().unwrap());
+          assert!(u64::from(increment) + u64::from(DEFAULT_WINDOW_SIZE) > u64::from(MAX_WINDOW_SIZE));
+        }
+        ViolationKind::ZeroWindowIncrement => {
+          assert_eq!(frames[1].payload, [0, 0, 0, 0]);
+        }
+        ViolationKind::DataExceedingWindow => {
+          let data = frames.iter().filter(|frame| frame.frame_type == 0x0).map(|frame| frame.payload.len()).sum::<usize>();
+          assert!(data > DEFAULT_WINDOW_SIZE as usize);
+          assert!(frames.iter().all(|frame| frame.payload.len() <= DEFAULT_MAX_FRAME_SIZE));
+        }
+        ViolationKind::InvalidSetting => assert_eq!(frames.len(), 1),
+        ViolationKind::UppercaseHeaderName => {
+          assert!(header_fields().iter().any(|field| field.name.chars().any(|c| c.is_ascii_uppercase())));
+        }
+        ViolationKind::ConnectionSpecificHeader => {
+          assert!(header_fields().iter().any(|field| {
+            CONNECTION_SPECIFIC_HEADERS.contains(&field.name.as_str())
+              || (field.name == "te" && field.value != "trailers")
+          }));
+        }
+        ViolationKind::PseudoHeaderAfterRegular => {
+          let fields = header_fields();
+          let first_regular = fields.iter().position(|field| !field.name.starts_with(':')).unwrap();
+          assert!(fields[first_regular..].iter().any(|field| field.name.starts_with(':')));
+        }
+      }
+
+      match violation.scope {
+        ErrorScope::Connection => {}
+        ErrorScope::Stream(stream_id) => {
+          assert!(frames.iter().any(|frame| frame.frame_type == 0x1 && frame.stream_id == stream_id));
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,319 @@
+//! HTTP/3 client connection strategies, without the QUIC transport.
+//!
+//! a connection is laid out as the octets of its streams: the control stream, the QPACK encoder
+//! stream, reserved unidirectional streams and one bidirectional request stream per request.
+//! Requests reuse the [`request_line`](crate::request_line) model through their pseudo header
+//! fields.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  bool::weighted,
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  sample::Index,
+};
+
+use crate::{
+  h2::connection::request_headers,
+  h3::{
+    frame::{Frame, Setting, encode_varint, reserved, reserved_frame, settings},
+    qpack::{FieldSection, encode_field_sections},
+  },
+  header::HeaderField,
+  request_line::{
+    pseudo_headers::{PseudoHeaders, pseudo_headers},
+    version::HttpVersion,
+  },
+};
+
+/// control stream type.
+pub const CONTROL_STREAM: u64 = 0x00;
+/// QPACK encoder stream type.
+pub const ENCODER_STREAM: u64 = 0x02;
+
+/// request sent on a client initiated bidirectional stream.
+#[derive(Debug, Clone)]
+pub struct Request {
+  /// QUIC stream identifier, a multiple of 4.
+  pub stream_id: u64,
+  pub pseudo_headers: PseudoHeaders,
+  /// regular header fields, following the pseudo header fields.
+  pub headers: Vec<HeaderField>,
+  pub body: Vec<u8>,
+}
+
+impl Request {
+  /// header list of the request: pseudo header fields followed by regular header fields.
+  pub fn header_list(&self) -> Vec<HeaderField> {
+    self
+      .pseudo_headers
+      .fields()
+      .into_iter()
+      .map(|(name, value)| HeaderField { name: name.to_string(), value })
+      .chain(self.headers.iter().cloned())
+      .collect()
+  }
+}
+
+/// HTTP/3 client connection.
+#[derive(Debug, Clone)]
+pub struct Connection {
+  /// client SETTINGS parameters.
+  pub settings: Vec<Setting>,
+  pub requests: Vec<Request>,
+  /// QPACK field section of every request.
+  pub field_sections: Vec<FieldSection>,
+  /// frames of the control stream, starting with SETTINGS.
+  pub control_frames: Vec<Frame>,
+  /// frames of every request stream.
+  pub request_frames: Vec<Vec<Frame>>,
+}
+
+/// octets sent on the streams of a connection.
+///
+/// unidirectional streams start with their stream type.
+#[derive(Debug, Clone)]
+pub struct Streams {
+  pub control: Vec<u8>,
+  /// QPACK encoder stream, to deliver before the request streams so that none is blocked.
+  pub encoder: Vec<u8>,
+  /// unidirectional streams of reserved types, which must be ignored.
+  pub reserved: Vec<Vec<u8>>,
+  /// request streams with their stream identifier.
+  pub requests: Vec<(u64, Vec<u8>)>,
+}
+
+/// random framing decisions for a request stream.
+#[derive(Debug, Clone)]
+struct StreamChoice {
+  data_splits: Vec<Index>,
+  /// reserved frames and the position at which they are inserted.
+  reserved: Vec<(Index, Frame)>,
+}
+
+fn stream_choice() -> impl Strategy<Value = StreamChoice> {
+  (vec(any::<Index>(), 0..=4), vec((any::<Index>(), reserved_frame()), 0..=2)).prop_map(
+    |(data_splits, reserved)| StreamChoice {
+      data_splits,
+      reserved: reserved.into_iter().map(|(index, (frame, _))| (index, frame)).collect(),
+    },
+  )
+}
+
+/// insert frames at the given positions.
+fn insert_frames(frames: &mut Vec<Frame>, inserted: &[(Index, Frame)]) {
+  for (index, frame) in inserted {
+    frames.insert(index.index(frames.len() + 1), frame.clone());
+  }
+}
+
+/// frames of a request stream: HEADERS followed by the body in DATA frames, interleaved with
+/// reserved frames.
+fn stream_frames(request: &Request, field_section: &[u8], choice: &StreamChoice) -> Vec<Frame> {
+  let mut frames = vec![Frame::Headers { field_section: field_section.to_vec() }];
+  if !request.body.is_empty() {
+    let mut positions = choice
+      .data_splits
+      .iter()
+      .map(|split| split.index(request.body.len() + 1))
+      .collect::<Vec<_>>();
+    positions.push(request.body.len());
+    positions.sort_unstable();
+
+    let mut start = 0;
+    for end in positions {
+      // empty DATA frames are valid, keep some of them.
+      if end > start || start == 0 {
+        frames.push(Frame::Data { data: request.body[start..end].to_vec() });
+      }
+      start = end;
+    }
+  }
+  insert_frames(&mut frames, &choice.reserved);
+  frames
+}
+
+fn encode_frames(frames: &[Frame], output: &mut Vec<u8>) {
+  for frame in frames {
+    frame.encode(output);
+  }
+}
+
+/// strategy for generating HTTP/3 client connection.
+///
+/// the control stream starts with a SETTINGS frame, followed by reserved frames and optionally
+/// a GOAWAY frame. The encoder stream carries the instructions of every field section. Every
+/// request is sent on its own stream as a HEADERS frame, followed by its body in DATA frames,
+/// with reserved frames interleaved. Reserved unidirectional streams may be opened.
+///
+/// the QPACK encoder assumes the server advertised a
+/// [`MAX_TABLE_CAPACITY`](crate::h3::qpack::MAX_TABLE_CAPACITY) dynamic table, and extended
+/// CONNECT requests (RFC 9220) are sent without waiting for `SETTINGS_ENABLE_CONNECT_PROTOCOL`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `stream_count_range`: range of the number of request streams.
+/// * `max_body_len`: maximum length of a request body.
+///
+/// # Returns
+/// [`Connection`] and its [`Streams`].
+pub fn connection(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  stream_count_range: RangeInclusive<usize>,
+  max_body_len: usize,
+) -> impl Strategy<Value = (Connection, Streams)> {
+  let request = (
+    pseudo_headers(max_label_count, max_segments, query_count_range),
+    request_headers(),
+    vec(any::<u8>(), 0..=max_body_len),
+    stream_choice(),
+  );
+  let reserved_stream = (reserved(), vec(any::<u8>(), 0..=32));
+
+  (
+    settings(0..=6),
+    vec(reserved_frame(), 0..=3),
+    weighted(0.3),
+    vec(reserved_stream, 0..=2),
+    vec(request, stream_count_range),
+  )
+    .prop_flat_map(|(settings, control_reserved, goaway, reserved_streams, requests)| {
+      let mut choices = Vec::with_capacity(requests.len());
+      let requests = (0u64..)
+        .zip(requests)
+        .map(|(i, ((mut pseudo_headers, _), headers, body, choice))| {
+          pseudo_headers.version = HttpVersion::Http3;
+          let body = if pseudo_headers.method.allows_body() { body } else { vec![] };
+          choices.push(choice);
+          Request { stream_id: 4 * i, pseudo_headers, headers, body }
+        })
+        .collect::<Vec<_>>();
+
+      let lists = requests.iter().map(Request::header_list).collect();
+
+      (
+        Just((settings, control_reserved, goaway, reserved_streams, requests, choices)),
+        encode_field_sections(lists),
+      )
+    })
+    .prop_map(
+      |(
+        (settings, control_reserved, goaway, reserved_streams, requests, choices),
+        (field_sections, encoded),
+      )| {
+        let mut control_frames = vec![Frame::Settings { settings: settings.clone() }];
+        control_frames.extend(control_reserved.into_iter().map(|(frame, _)| frame));
+        if goaway {
+          control_frames.push(Frame::GoAway { id: 0 });
+        }
+
+        let mut control = vec![];
+        encode_varint(CONTROL_STREAM, &mut control);
+        encode_frames(&control_frames, &mut control);
+
+        let mut encoder = vec![];
+        encode_varint(ENCODER_STREAM, &mut encoder);
+        for section in &encoded {
+          encoder.extend_from_slice(&section.encoder_stream);
+        }
+
+        let reserved = reserved_streams
+          .into_iter()
+          .map(|(stream_type, payload)| {
+            let mut stream = vec![];
+            encode_varint(stream_type, &mut stream);
+            stream.extend_from_slice(&payload);
+            stream
+          })
+          .collect();
+
+        let request_frames = requests
+          .iter()
+          .zip(&encoded)
+          .zip(&choices)
+          .map(|((request, section), choice)| {
+            stream_frames(request, &section.field_section, choice)
+          })
+          .collect::<Vec<_>>();
+        let request_streams = requests
+          .iter()
+          .zip(&request_frames)
+          .map(|(request, frames)| {
+            let mut stream = vec![];
+            encode_frames(frames, &mut stream);
+            (request.stream_id, stream)
+          })
+          .collect();
+
+        (
+          Connection { settings, requests, field_sections, control_frames, request_frames },
+          Streams { control, encoder, reserved, requests: request_streams },
+        )
+      },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::h3::{
+    frame::{
+      is_reserved,
+      tests::{decode_varint, parse_frames, parse_settings},
+    },
+    qpack::tests::Decoder,
+  };
+
+  proptest! {
+    #[test]
+    fn connection_works(
+      (connection, streams) in connection(20, 50.try_into().unwrap(), 0..=10, 1..=5, 1024)
+    ) {
+      let mut position = 0;
+      assert_eq!(decode_varint(&streams.control, &mut position), CONTROL_STREAM);
+      let control_frames = parse_frames(&streams.control[position..]);
+      assert_eq!(control_frames.len(), connection.control_frames.len());
+      assert_eq!(control_frames[0].frame_type, 0x4, "first frame should be SETTINGS");
+      assert_eq!(parse_settings(control_frames[0].payload).len(), connection.settings.len());
+      for frame in &control_frames[1..] {
+        assert!(frame.frame_type == 0x7 || is_reserved(frame.frame_type));
+      }
+
+      for stream in &streams.reserved {
+        assert!(is_reserved(decode_varint(stream, &mut 0)), "stream type should be reserved");
+      }
+
+      let mut decoder = Decoder::default();
+      let mut position = 0;
+      assert_eq!(decode_varint(&streams.encoder, &mut position), ENCODER_STREAM);
+      decoder.process_encoder_stream(&streams.encoder[position..]);
+
+      assert_eq!(streams.requests.len(), connection.requests.len());
+      for ((stream_id, stream), request) in streams.requests.iter().zip(&connection.requests) {
+        assert_eq!(*stream_id, request.stream_id);
+        assert_eq!(stream_id % 4, 0, "request stream should be client bidirectional");
+
+        let mut frames = parse_frames(stream)
+          .into_iter()
+          .filter(|frame| !is_reserved(frame.frame_type));
+        let headers = frames.next().expect("HEADERS frame");
+        assert_eq!(headers.frame_type, 0x1);
+        assert_eq!(decoder.decode_field_section(headers.payload), request.header_list());
+
+        let mut body = vec![];
+        for frame in frames {
+          assert_eq!(frame.frame_type, 0x0, "expected DATA");
+          body.extend_from_slice(frame.payload);
+        }
+        assert_eq!(body, request.body);
+      }
+    }
+  }
+}
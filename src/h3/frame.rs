@@ -0,0 +1,372 @@
+//! HTTP/3 frame (RFC 9114 section 7) strategies.
+//!
+//! frame type, length and most integer fields are QUIC variable-length integers (RFC 9000
+//! section 16).
+
+use std::ops::RangeInclusive;
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+};
+
+use crate::h3::qpack::{EncodedFieldSection, FieldSection, field_section};
+
+/// maximum value of a variable-length integer.
+pub const MAX_VARINT: u64 = (1 << 62) - 1;
+
+const DATA: u64 = 0x0;
+const HEADERS: u64 = 0x1;
+const SETTINGS: u64 = 0x4;
+const GOAWAY: u64 = 0x7;
+
+/// encode variable-length integer on the minimum number of octets.
+///
+/// # Panics
+/// if the value exceeds [`MAX_VARINT`].
+pub fn encode_varint(value: u64, output: &mut Vec<u8>) {
+  assert!(value <= MAX_VARINT, "varint {value} out of range");
+  match value {
+    0..=0x3f => output.push(value as u8),
+    0x40..=0x3fff => output.extend_from_slice(&(value as u16 | 0x4000).to_be_bytes()),
+    0x4000..=0x3fff_ffff => output.extend_from_slice(&(value as u32 | 0x8000_0000).to_be_bytes()),
+    _ => output.extend_from_slice(&(value | 0xc000_0000_0000_0000).to_be_bytes()),
+  }
+}
+
+/// reserved identifier for greasing, `0x1f * N + 0x21` (RFC 9114 sections 7.2.8, 7.2.4.1 and
+/// 6.2.3), used for frame types, setting identifiers and stream types.
+pub fn is_reserved(value: u64) -> bool {
+  value >= 0x21 && (value - 0x21) % 0x1f == 0
+}
+
+/// SETTINGS parameter identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingId {
+  /// `SETTINGS_QPACK_MAX_TABLE_CAPACITY` (RFC 9204).
+  QpackMaxTableCapacity,
+  MaxFieldSectionSize,
+  /// `SETTINGS_QPACK_BLOCKED_STREAMS` (RFC 9204).
+  QpackBlockedStreams,
+  /// `SETTINGS_ENABLE_CONNECT_PROTOCOL` (RFC 9220).
+  EnableConnectProtocol,
+  /// `SETTINGS_H3_DATAGRAM` (RFC 9297).
+  H3Datagram,
+  /// reserved identifiers must be ignored.
+  Reserved(u64),
+}
+
+impl SettingId {
+  fn code(self) -> u64 {
+    match self {
+      SettingId::QpackMaxTableCapacity => 0x1,
+      SettingId::MaxFieldSectionSize => 0x6,
+      SettingId::QpackBlockedStreams => 0x7,
+      SettingId::EnableConnectProtocol => 0x8,
+      SettingId::H3Datagram => 0x33,
+      SettingId::Reserved(code) => code,
+    }
+  }
+}
+
+/// SETTINGS parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setting {
+  pub id: SettingId,
+  pub value: u64,
+}
+
+/// HTTP/3 frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+  Data {
+    data: Vec<u8>,
+  },
+  Headers {
+    /// encoded QPACK field section.
+    field_section: Vec<u8>,
+  },
+  Settings {
+    settings: Vec<Setting>,
+  },
+  GoAway {
+    /// stream identifier, or push identifier when sent by the client.
+    id: u64,
+  },
+  /// reserved frame type, which must be ignored.
+  Reserved {
+    frame_type: u64,
+    payload: Vec<u8>,
+  },
+}
+
+impl Frame {
+  /// encode the frame: type, length and payload.
+  pub fn encode(&self, output: &mut Vec<u8>) {
+    let mut payload = vec![];
+    let frame_type = match self {
+      Frame::Data { data } => {
+        payload.extend_from_slice(data);
+        DATA
+      }
+      Frame::Headers { field_section } => {
+        payload.extend_from_slice(field_section);
+        HEADERS
+      }
+      Frame::Settings { settings } => {
+        for setting in settings {
+          encode_varint(setting.id.code(), &mut payload);
+          encode_varint(setting.value, &mut payload);
+        }
+        SETTINGS
+      }
+      Frame::GoAway { id } => {
+        encode_varint(*id, &mut payload);
+        GOAWAY
+      }
+      Frame::Reserved { frame_type, payload: reserved } => {
+        payload.extend_from_slice(reserved);
+        *frame_type
+      }
+    };
+
+    encode_varint(frame_type, output);
+    encode_varint(payload.len() as u64, output);
+    output.extend_from_slice(&payload);
+  }
+
+  /// encoded frame.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut output = vec![];
+    self.encode(&mut output);
+    output
+  }
+}
+
+/// strategy for generating variable-length integer, evenly spread over the encoding lengths.
+pub fn varint() -> impl Strategy<Value = u64> {
+  prop_oneof![0..=0x3fu64, 0x40..=0x3fffu64, 0x4000..=0x3fff_ffffu64, 0x4000_0000..=MAX_VARINT]
+}
+
+/// strategy for generating reserved identifier, `0x1f * N + 0x21`.
+pub fn reserved() -> impl Strategy<Value = u64> {
+  prop_oneof![
+    3 => 0..=16u64,
+    1 => 0..=(MAX_VARINT - 0x21) / 0x1f,
+  ]
+  .prop_map(|n| 0x1f * n + 0x21)
+}
+
+/// strategy for generating valid SETTINGS parameter.
+pub fn setting() -> impl Strategy<Value = Setting> {
+  prop_oneof![
+    varint().prop_map(|value| Setting { id: SettingId::QpackMaxTableCapacity, value }),
+    varint().prop_map(|value| Setting { id: SettingId::MaxFieldSectionSize, value }),
+    varint().prop_map(|value| Setting { id: SettingId::QpackBlockedStreams, value }),
+    (0..=1u64).prop_map(|value| Setting { id: SettingId::EnableConnectProtocol, value }),
+    (0..=1u64).prop_map(|value| Setting { id: SettingId::H3Datagram, value }),
+    (reserved(), varint())
+      .prop_map(|(code, value)| Setting { id: SettingId::Reserved(code), value }),
+  ]
+}
+
+/// strategy for generating SETTINGS parameters, each identifier appearing at most once.
+///
+/// # Arguments
+/// * `count_range`: range of the number of generated parameters, before deduplication.
+pub fn settings(count_range: RangeInclusive<usize>) -> impl Strategy<Value = Vec<Setting>> {
+  vec(setting(), count_range).prop_map(|settings| {
+    let mut unique = Vec::<Setting>::with_capacity(settings.len());
+    for setting in settings {
+      if unique.iter().all(|other| other.id.code() != setting.id.code()) {
+        unique.push(setting);
+      }
+    }
+    unique
+  })
+}
+
+/// strategy for generating DATA frame.
+///
+/// # Arguments
+/// * `len_range`: range of the data length.
+///
+/// # Returns
+/// [`Frame::Data`] and its encoding.
+pub fn data_frame(len_range: RangeInclusive<usize>) -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  vec(any::<u8>(), len_range).prop_map(|data| {
+    let frame = Frame::Data { data };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating HEADERS frame carrying a QPACK field section.
+///
+/// the field section may reference the dynamic table, it can only be decoded after the encoder
+/// stream instructions.
+///
+/// # Arguments
+/// * `count_range`: range of the number of header fields.
+///
+/// # Returns
+/// [`FieldSection`], its encoding, [`Frame::Headers`] and its encoding.
+pub fn headers_frame(
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = ((FieldSection, EncodedFieldSection), (Frame, Vec<u8>))> {
+  field_section(count_range).prop_map(|(section, encoded)| {
+    let frame = Frame::Headers { field_section: encoded.field_section.clone() };
+    let repr = frame.to_bytes();
+    ((section, encoded), (frame, repr))
+  })
+}
+
+/// strategy for generating SETTINGS frame.
+///
+/// # Returns
+/// [`Frame::Settings`] and its encoding.
+pub fn settings_frame() -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  settings(0..=8).prop_map(|settings| {
+    let frame = Frame::Settings { settings };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating GOAWAY frame.
+///
+/// # Returns
+/// [`Frame::GoAway`] and its encoding.
+pub fn goaway_frame() -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  varint().prop_map(|id| {
+    let frame = Frame::GoAway { id };
+    let repr = frame.to_bytes();
+    (frame, repr)
+  })
+}
+
+/// strategy for generating reserved frame, with an arbitrary payload.
+///
+/// # Returns
+/// [`Frame::Reserved`] and its encoding.
+pub fn reserved_frame() -> impl Strategy<Value = (Frame, Vec<u8>)> {
+  (reserved(), prop_oneof![Just(vec![]), vec(any::<u8>(), 0..=64)]).prop_map(
+    |(frame_type, payload)| {
+      let frame = Frame::Reserved { frame_type, payload };
+      let repr = frame.to_bytes();
+      (frame, repr)
+    },
+  )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  /// decode variable-length integer.
+  pub(crate) fn decode_varint(input: &[u8], position: &mut usize) -> u64 {
+    let len = 1 << (input[*position] >> 6);
+    let mut value = u64::from(input[*position] & 0x3f);
+    for octet in &input[*position + 1..*position + len] {
+      value = (value << 8) | u64::from(*octet);
+    }
+    *position += len;
+    value
+  }
+
+  /// parsed frame type and payload.
+  #[derive(Debug)]
+  pub(crate) struct RawFrame<'a> {
+    pub(crate) frame_type: u64,
+    pub(crate) payload: &'a [u8],
+  }
+
+  /// split octets into frames.
+  pub(crate) fn parse_frames(input: &[u8]) -> Vec<RawFrame<'_>> {
+    let mut frames = vec![];
+    let mut position = 0;
+    while position < input.len() {
+      let frame_type = decode_varint(input, &mut position);
+      let length = decode_varint(input, &mut position) as usize;
+      assert!(position + length <= input.len(), "truncated frame {input:?}");
+      frames.push(RawFrame { frame_type, payload: &input[position..position + length] });
+      position += length;
+    }
+    frames
+  }
+
+  /// decode SETTINGS payload.
+  pub(crate) fn parse_settings(payload: &[u8]) -> Vec<(u64, u64)> {
+    let mut settings = vec![];
+    let mut position = 0;
+    while position < payload.len() {
+      let id = decode_varint(payload, &mut position);
+      settings.push((id, decode_varint(payload, &mut position)));
+    }
+    settings
+  }
+
+  fn frame_asserts(frame: &Frame, repr: &[u8]) {
+    let raw_frames = parse_frames(repr);
+    assert_eq!(raw_frames.len(), 1, "expected a single frame in {repr:?}");
+    let raw_frame = &raw_frames[0];
+
+    match frame {
+      Frame::Data { data } => {
+        assert_eq!(raw_frame.frame_type, DATA);
+        assert_eq!(raw_frame.payload, data);
+      }
+      Frame::Headers { field_section } => {
+        assert_eq!(raw_frame.frame_type, HEADERS);
+        assert_eq!(raw_frame.payload, field_section);
+      }
+      Frame::Settings { settings } => {
+        assert_eq!(raw_frame.frame_type, SETTINGS);
+        let parsed = parse_settings(raw_frame.payload);
+        assert_eq!(parsed.len(), settings.len());
+        for ((id, value), setting) in parsed.iter().zip(settings) {
+          assert_eq!(*id, setting.id.code());
+          assert_eq!(*value, setting.value);
+        }
+      }
+      Frame::GoAway { id } => {
+        assert_eq!(raw_frame.frame_type, GOAWAY);
+        assert_eq!(decode_varint(raw_frame.payload, &mut 0), *id);
+      }
+      Frame::Reserved { frame_type, payload } => {
+        assert!(is_reserved(raw_frame.frame_type), "frame type should be reserved");
+        assert_eq!(raw_frame.frame_type, *frame_type);
+        assert_eq!(raw_frame.payload, payload);
+      }
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn varint_works(value in varint()) {
+      let mut output = vec![];
+      encode_varint(value, &mut output);
+      assert!([1, 2, 4, 8].contains(&output.len()));
+      assert_eq!(decode_varint(&output, &mut 0), value);
+      if output.len() > 1 {
+        assert!(value >= 1 << (8 * output.len() / 2 - 2), "varint {value} should be shorter");
+      }
+    }
+
+    #[test]
+    fn frames_works(
+      (frame, repr) in prop_oneof![
+        data_frame(0..=1024),
+        headers_frame(0..=10).prop_map(|(_, frame)| frame),
+        settings_frame(),
+        goaway_frame(),
+        reserved_frame(),
+      ]
+    ) {
+      frame_asserts(&frame, &repr);
+    }
+  }
+}
@@ -0,0 +1,5 @@
+//! HTTP/3 strategies.
+
+pub mod connection;
+pub mod frame;
+pub mod qpack;
@@ -0,0 +1,823 @@
+//! QPACK (RFC 9204) field section strategies.
+//!
+//! header lists are encoded as field sections referencing the static table, the dynamic table
+//! before and after the base, or literals, together with the encoder stream instructions that
+//! populate the dynamic table. The decoded header list is the model.
+
+use std::ops::RangeInclusive;
+
+use proptest::{
+  bool::weighted,
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+  sample::Index,
+};
+
+use crate::{
+  h2::hpack::{encode_integer, encode_string, header_list_with},
+  header::HeaderField,
+};
+
+/// `SETTINGS_QPACK_MAX_TABLE_CAPACITY` of the decoder.
+pub const MAX_TABLE_CAPACITY: usize = 4096;
+
+/// overhead added to the length of name and value to get the size of a dynamic table entry.
+const ENTRY_OVERHEAD: usize = 32;
+
+/// QPACK static table (RFC 9204 appendix A), the index of an entry is its position.
+pub(crate) const STATIC_TABLE: [(&str, &str); 99] = [
+  (":authority", ""),
+  (":path", "/"),
+  ("age", "0"),
+  ("content-disposition", ""),
+  ("content-length", "0"),
+  ("cookie", ""),
+  ("date", ""),
+  ("etag", ""),
+  ("if-modified-since", ""),
+  ("if-none-match", ""),
+  ("last-modified", ""),
+  ("link", ""),
+  ("location", ""),
+  ("referer", ""),
+  ("set-cookie", ""),
+  (":method", "CONNECT"),
+  (":method", "DELETE"),
+  (":method", "GET"),
+  (":method", "HEAD"),
+  (":method", "OPTIONS"),
+  (":method", "POST"),
+  (":method", "PUT"),
+  (":scheme", "http"),
+  (":scheme", "https"),
+  (":status", "103"),
+  (":status", "200"),
+  (":status", "304"),
+  (":status", "404"),
+  (":status", "503"),
+  ("accept", "*/*"),
+  ("accept", "application/dns-message"),
+  ("accept-encoding", "gzip, deflate, br"),
+  ("accept-ranges", "bytes"),
+  ("access-control-allow-headers", "cache-control"),
+  ("access-control-allow-headers", "content-type"),
+  ("access-control-allow-origin", "*"),
+  ("cache-control", "max-age=0"),
+  ("cache-control", "max-age=2592000"),
+  ("cache-control", "max-age=604800"),
+  ("cache-control", "no-cache"),
+  ("cache-control", "no-store"),
+  ("cache-control", "public, max-age=31536000"),
+  ("content-encoding", "br"),
+  ("content-encoding", "gzip"),
+  ("content-type", "application/dns-message"),
+  ("content-type", "application/javascript"),
+  ("content-type", "application/json"),
+  ("content-type", "application/x-www-form-urlencoded"),
+  ("content-type", "image/gif"),
+  ("content-type", "image/jpeg"),
+  ("content-type", "image/png"),
+  ("content-type", "text/css"),
+  ("content-type", "text/html; charset=utf-8"),
+  ("content-type", "text/plain"),
+  ("content-type", "text/plain;charset=utf-8"),
+  ("range", "bytes=0-"),
+  ("strict-transport-security", "max-age=31536000"),
+  ("strict-transport-security", "max-age=31536000; includesubdomains"),
+  ("strict-transport-security", "max-age=31536000; includesubdomains; preload"),
+  ("vary", "accept-encoding"),
+  ("vary", "origin"),
+  ("x-content-type-options", "nosniff"),
+  ("x-xss-protection", "1; mode=block"),
+  (":status", "100"),
+  (":status", "204"),
+  (":status", "206"),
+  (":status", "302"),
+  (":status", "400"),
+  (":status", "403"),
+  (":status", "421"),
+  (":status", "425"),
+  (":status", "500"),
+  ("accept-language", ""),
+  ("access-control-allow-credentials", "FALSE"),
+  ("access-control-allow-credentials", "TRUE"),
+  ("access-control-allow-headers", "*"),
+  ("access-control-allow-methods", "get"),
+  ("access-control-allow-methods", "get, post, options"),
+  ("access-control-allow-methods", "options"),
+  ("access-control-expose-headers", "content-length"),
+  ("access-control-request-headers", "content-type"),
+  ("access-control-request-method", "get"),
+  ("access-control-request-method", "post"),
+  ("alt-svc", "clear"),
+  ("authorization", ""),
+  ("content-security-policy", "script-src 'none'; object-src 'none'; base-uri 'none'"),
+  ("early-data", "1"),
+  ("expect-ct", ""),
+  ("forwarded", ""),
+  ("if-range", ""),
+  ("origin", ""),
+  ("purpose", "prefetch"),
+  ("server", ""),
+  ("timing-allow-origin", "*"),
+  ("upgrade-insecure-requests", "1"),
+  ("user-agent", ""),
+  ("x-forwarded-for", ""),
+  ("x-frame-options", "deny"),
+  ("x-frame-options", "sameorigin"),
+];
+
+/// encoder stream instruction.
+///
+/// dynamic table indices are relative to the insert count when the instruction is sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncoderInstruction {
+  SetDynamicTableCapacity(usize),
+  InsertWithNameReference { is_static: bool, index: usize, huffman_value: bool },
+  InsertWithLiteralName { huffman_name: bool, huffman_value: bool },
+  Duplicate(usize),
+}
+
+/// field line representation.
+///
+/// dynamic table indices are relative to the base, post-base indices start at the base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldLineRepresentation {
+  Indexed {
+    is_static: bool,
+    index: usize,
+  },
+  IndexedPostBase(usize),
+  LiteralWithNameReference {
+    never_indexed: bool,
+    is_static: bool,
+    index: usize,
+    huffman_value: bool,
+  },
+  LiteralWithPostBaseNameReference {
+    never_indexed: bool,
+    index: usize,
+    huffman_value: bool,
+  },
+  LiteralWithLiteralName {
+    never_indexed: bool,
+    huffman_name: bool,
+    huffman_value: bool,
+  },
+}
+
+/// QPACK field section.
+#[derive(Debug, Clone)]
+pub struct FieldSection {
+  /// decoded header list.
+  pub fields: Vec<HeaderField>,
+  pub required_insert_count: usize,
+  pub base: usize,
+  /// encoder stream instructions to process before decoding the field section.
+  pub encoder_instructions: Vec<EncoderInstruction>,
+  /// field line representations, in order.
+  pub representations: Vec<FieldLineRepresentation>,
+}
+
+/// encoded QPACK field section.
+#[derive(Debug, Clone)]
+pub struct EncodedFieldSection {
+  /// encoder stream bytes to deliver before the field section.
+  pub encoder_stream: Vec<u8>,
+  /// field section bytes, as carried by a HEADERS frame.
+  pub field_section: Vec<u8>,
+}
+
+/// random encoding decisions for a single header field.
+#[derive(Debug, Clone)]
+struct FieldChoice {
+  insert: bool,
+  duplicate: bool,
+  indexed: bool,
+  name_reference: bool,
+  never_indexed: bool,
+  huffman_name: bool,
+  huffman_value: bool,
+}
+
+fn field_choice() -> impl Strategy<Value = FieldChoice> {
+  (
+    weighted(0.5),
+    weighted(0.3),
+    weighted(0.7),
+    weighted(0.7),
+    weighted(0.2),
+    weighted(0.5),
+    weighted(0.5),
+  )
+    .prop_map(
+      |(insert, duplicate, indexed, name_reference, never_indexed, huffman_name, huffman_value)| {
+        FieldChoice {
+          insert,
+          duplicate,
+          indexed,
+          name_reference,
+          never_indexed,
+          huffman_name,
+          huffman_value,
+        }
+      },
+    )
+}
+
+/// reference to a table entry.
+#[derive(Debug, Clone, Copy)]
+enum Reference {
+  Static(usize),
+  /// absolute index in the dynamic table.
+  Dynamic(usize),
+}
+
+/// how a field line is encoded, before the base is known.
+enum FieldLine {
+  Indexed(Reference),
+  NameReference(Reference),
+  LiteralName,
+}
+
+/// size of a dynamic table entry.
+fn entry_size(field: &HeaderField) -> usize {
+  field.name.len() + field.value.len() + ENTRY_OVERHEAD
+}
+
+/// find field in static table.
+///
+/// # Returns
+/// index of the whole field, and index of the field name.
+fn find_static(field: &HeaderField) -> (Option<usize>, Option<usize>) {
+  let exact =
+    STATIC_TABLE.iter().position(|(name, value)| *name == field.name && *value == field.value);
+  let name = STATIC_TABLE.iter().position(|(name, _)| *name == field.name);
+  (exact, name)
+}
+
+/// QPACK encoder state shared by all the field sections of a connection.
+///
+/// entries are never evicted, a field is only inserted if it fits in the remaining capacity.
+struct Encoder {
+  /// dynamic table, the absolute index of an entry is its position.
+  dynamic_table: Vec<HeaderField>,
+  size: usize,
+  capacity: usize,
+}
+
+impl Encoder {
+  /// find field in dynamic table, newest entries first.
+  ///
+  /// # Returns
+  /// absolute index of the whole field, and absolute index of the field name.
+  fn find_dynamic(&self, field: &HeaderField) -> (Option<usize>, Option<usize>) {
+    let exact = self.dynamic_table.iter().rposition(|entry| entry == field);
+    let name = self.dynamic_table.iter().rposition(|entry| entry.name == field.name);
+    (exact, name)
+  }
+
+  /// relative index of an absolute index in the encoder stream.
+  fn relative(&self, absolute: usize) -> usize {
+    self.dynamic_table.len() - 1 - absolute
+  }
+
+  fn insert(
+    &mut self,
+    field: &HeaderField,
+    choice: &FieldChoice,
+    instructions: &mut Vec<EncoderInstruction>,
+    output: &mut Vec<u8>,
+  ) -> Option<usize> {
+    if self.size + entry_size(field) > self.capacity {
+      return None;
+    }
+
+    let (_, static_name) = find_static(field);
+    let (dynamic_exact, dynamic_name) = self.find_dynamic(field);
+    let instruction = if choice.duplicate
+      && let Some(absolute) = dynamic_exact
+    {
+      let index = self.relative(absolute);
+      encode_integer(index, 5, 0x00, output);
+      EncoderInstruction::Duplicate(index)
+    } else if let Some(index) = static_name {
+      encode_integer(index, 6, 0x80 | 0x40, output);
+      encode_string(field.value.as_bytes(), choice.huffman_value, 7, 0, output);
+      EncoderInstruction::InsertWithNameReference {
+        is_static: true,
+        index,
+        huffman_value: choice.huffman_value,
+      }
+    } else if let Some(absolute) = dynamic_name {
+      let index = self.relative(absolute);
+      encode_integer(index, 6, 0x80, output);
+      encode_string(field.value.as_bytes(), choice.huffman_value, 7, 0, output);
+      EncoderInstruction::InsertWithNameReference {
+        is_static: false,
+        index,
+        huffman_value: choice.huffman_value,
+      }
+    } else {
+      encode_string(field.name.as_bytes(), choice.huffman_name, 5, 0x40, output);
+      encode_string(field.value.as_bytes(), choice.huffman_value, 7, 0, output);
+      EncoderInstruction::InsertWithLiteralName {
+        huffman_name: choice.huffman_name,
+        huffman_value: choice.huffman_value,
+      }
+    };
+
+    instructions.push(instruction);
+    self.size += entry_size(field);
+    self.dynamic_table.push(field.clone());
+    Some(self.dynamic_table.len() - 1)
+  }
+
+  fn encode(
+    &mut self,
+    fields: Vec<HeaderField>,
+    choices: &[FieldChoice],
+    base: Index,
+    mut instructions: Vec<EncoderInstruction>,
+    mut encoder_stream: Vec<u8>,
+  ) -> (FieldSection, EncodedFieldSection) {
+    let insert_count = self.dynamic_table.len();
+
+    let mut lines = Vec::with_capacity(fields.len());
+    for (field, choice) in fields.iter().zip(choices) {
+      let (static_exact, static_name) = find_static(field);
+      let (mut dynamic_exact, dynamic_name) = self.find_dynamic(field);
+      if choice.insert && static_exact.is_none() {
+        dynamic_exact =
+          self.insert(field, choice, &mut instructions, &mut encoder_stream).or(dynamic_exact);
+      }
+
+      let exact = static_exact.map(Reference::Static).or(dynamic_exact.map(Reference::Dynamic));
+      let name = static_name.map(Reference::Static).or(dynamic_name.map(Reference::Dynamic));
+      lines.push(
+        if choice.indexed
+          && let Some(reference) = exact
+        {
+          FieldLine::Indexed(reference)
+        } else if choice.name_reference
+          && let Some(reference) = name.or(exact)
+        {
+          FieldLine::NameReference(reference)
+        } else {
+          FieldLine::LiteralName
+        },
+      );
+    }
+
+    let required_insert_count = lines
+      .iter()
+      .filter_map(|line| match line {
+        FieldLine::Indexed(Reference::Dynamic(absolute))
+        | FieldLine::NameReference(Reference::Dynamic(absolute)) => Some(absolute + 1),
+        _ => None,
+      })
+      .max()
+      .unwrap_or_default();
+    let base = if required_insert_count == 0 {
+      0
+    } else {
+      insert_count + base.index(self.dynamic_table.len() - insert_count + 1)
+    };
+
+    let mut field_section = vec![];
+    let max_entries = MAX_TABLE_CAPACITY / ENTRY_OVERHEAD;
+    let encoded_insert_count =
+      if required_insert_count == 0 { 0 } else { required_insert_count % (2 * max_entries) + 1 };
+    encode_integer(encoded_insert_count, 8, 0, &mut field_section);
+    if base >= required_insert_count {
+      encode_integer(base - required_insert_count, 7, 0, &mut field_section);
+    } else {
+      encode_integer(required_insert_count - base - 1, 7, 0x80, &mut field_section);
+    }
+
+    let mut representations = Vec::with_capacity(fields.len());
+    for ((field, choice), line) in fields.iter().zip(choices).zip(lines) {
+      let never_indexed = choice.never_indexed;
+      let huffman_value = choice.huffman_value;
+      let representation = match line {
+        FieldLine::Indexed(Reference::Static(index)) => {
+          encode_integer(index, 6, 0xc0, &mut field_section);
+          FieldLineRepresentation::Indexed { is_static: true, index }
+        }
+        FieldLine::Indexed(Reference::Dynamic(absolute)) if absolute < base => {
+          let index = base - 1 - absolute;
+          encode_integer(index, 6, 0x80, &mut field_section);
+          FieldLineRepresentation::Indexed { is_static: false, index }
+        }
+        FieldLine::Indexed(Reference::Dynamic(absolute)) => {
+          let index = absolute - base;
+          encode_integer(index, 4, 0x10, &mut field_section);
+          FieldLineRepresentation::IndexedPostBase(index)
+        }
+        FieldLine::NameReference(reference) => {
+          let n = if never_indexed { 0x20 } else { 0 };
+          let representation = match reference {
+            Reference::Static(index) => {
+              encode_integer(index, 4, 0x40 | n | 0x10, &mut field_section);
+              FieldLineRepresentation::LiteralWithNameReference {
+                never_indexed,
+                is_static: true,
+                index,
+                huffman_value,
+              }
+            }
+            Reference::Dynamic(absolute) if absolute < base => {
+              let index = base - 1 - absolute;
+              encode_integer(index, 4, 0x40 | n, &mut field_section);
+              FieldLineRepresentation::LiteralWithNameReference {
+                never_indexed,
+                is_static: false,
+                index,
+                huffman_value,
+              }
+            }
+            Reference::Dynamic(absolute) => {
+              let index = absolute - base;
+              encode_integer(index, 3, if never_indexed { 0x08 } else { 0 }, &mut field_section);
+              FieldLineRepresentation::LiteralWithPostBaseNameReference {
+                never_indexed,
+                index,
+                huffman_value,
+              }
+            }
+          };
+          encode_string(field.value.as_bytes(), huffman_value, 7, 0, &mut field_section);
+          representation
+        }
+        FieldLine::LiteralName => {
+          let n = if never_indexed { 0x10 } else { 0 };
+          encode_string(
+            field.name.as_bytes(),
+            choice.huffman_name,
+            3,
+            0x20 | n,
+            &mut field_section,
+          );
+          encode_string(field.value.as_bytes(), huffman_value, 7, 0, &mut field_section);
+          FieldLineRepresentation::LiteralWithLiteralName {
+            never_indexed,
+            huffman_name: choice.huffman_name,
+            huffman_value,
+          }
+        }
+      };
+      representations.push(representation);
+    }
+
+    (
+      FieldSection {
+        fields,
+        required_insert_count,
+        base,
+        encoder_instructions: instructions,
+        representations,
+      },
+      EncodedFieldSection { encoder_stream, field_section },
+    )
+  }
+}
+
+/// strategy for generating the dynamic table capacity set by the encoder.
+fn capacity() -> impl Strategy<Value = usize> {
+  prop_oneof![
+    3 => Just(MAX_TABLE_CAPACITY),
+    1 => 0..=MAX_TABLE_CAPACITY,
+  ]
+}
+
+/// strategy for generating header list likely to hit the static and dynamic tables.
+///
+/// see [`crate::h2::hpack::header_list`].
+///
+/// # Arguments
+/// * `count_range`: range of the number of header fields.
+pub fn header_list(count_range: RangeInclusive<usize>) -> impl Strategy<Value = Vec<HeaderField>> {
+  header_list_with(&STATIC_TABLE, count_range)
+}
+
+/// strategy for encoding header lists as QPACK field sections of the same connection.
+///
+/// the encoder stream of the first field section starts by setting the dynamic table capacity.
+/// Encoder stream bytes of a field section must be processed before decoding it, so the field
+/// sections never block.
+///
+/// # Arguments
+/// * `lists`: header lists to encode.
+///
+/// # Returns
+/// [`FieldSection`] and its encoding for every header list.
+pub fn encode_field_sections(
+  lists: Vec<Vec<HeaderField>>,
+) -> impl Strategy<Value = (Vec<FieldSection>, Vec<EncodedFieldSection>)> {
+  let choices =
+    lists.iter().map(|list| (vec(field_choice(), list.len()), any::<Index>())).collect::<Vec<_>>();
+
+  (Just(lists), capacity(), choices).prop_map(|(lists, capacity, choices)| {
+    let mut encoder = Encoder { dynamic_table: vec![], size: 0, capacity };
+    let mut instructions = vec![EncoderInstruction::SetDynamicTableCapacity(capacity)];
+    let mut encoder_stream = vec![];
+    encode_integer(capacity, 5, 0x20, &mut encoder_stream);
+
+    lists
+      .into_iter()
+      .zip(choices)
+      .map(|(fields, (choices, base))| {
+        encoder.encode(
+          fields,
+          &choices,
+          base,
+          std::mem::take(&mut instructions),
+          std::mem::take(&mut encoder_stream),
+        )
+      })
+      .unzip()
+  })
+}
+
+/// strategy for generating QPACK field section.
+///
+/// # Arguments
+/// * `count_range`: range of the number of header fields.
+///
+/// # Returns
+/// [`FieldSection`] and its encoding.
+pub fn field_section(
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (FieldSection, EncodedFieldSection)> {
+  header_list(count_range).prop_flat_map(|list| {
+    encode_field_sections(vec![list]).prop_map(|(mut sections, mut reprs)| {
+      (sections.pop().expect("one field section"), reprs.pop().expect("one field section"))
+    })
+  })
+}
+
+/// strategy for generating QPACK field sections of the same connection.
+///
+/// see [`encode_field_sections`].
+///
+/// # Arguments
+/// * `section_count_range`: range of the number of field sections.
+/// * `count_range`: range of the number of header fields per field section.
+///
+/// # Returns
+/// [`FieldSection`] and its encoding for every field section.
+pub fn field_sections(
+  section_count_range: RangeInclusive<usize>,
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Vec<FieldSection>, Vec<EncodedFieldSection>)> {
+  vec(header_list(count_range), section_count_range).prop_flat_map(encode_field_sections)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::{
+    proptest,
+    strategy::{Strategy, ValueTree},
+  };
+
+  use super::*;
+  use crate::h2::hpack::huffman::tests::huffman_decode;
+
+  fn decode_integer(input: &[u8], position: &mut usize, prefix_bits: u8) -> usize {
+    let max_prefix = (1usize << prefix_bits) - 1;
+    let mut value = usize::from(input[*position]) & max_prefix;
+    *position += 1;
+    if value < max_prefix {
+      return value;
+    }
+
+    let mut shift = 0;
+    loop {
+      let octet = input[*position];
+      *position += 1;
+      value += usize::from(octet & 0x7f) << shift;
+      shift += 7;
+      if octet & 0x80 == 0 {
+        return value;
+      }
+    }
+  }
+
+  fn decode_string(input: &[u8], position: &mut usize, prefix_bits: u8) -> String {
+    let huffman = input[*position] & (1 << prefix_bits) != 0;
+    let length = decode_integer(input, position, prefix_bits);
+    let octets = &input[*position..*position + length];
+    *position += length;
+    let octets = if huffman { huffman_decode(octets) } else { octets.to_vec() };
+    String::from_utf8(octets).unwrap()
+  }
+
+  fn static_field(index: usize) -> HeaderField {
+    let (name, value) = STATIC_TABLE[index];
+    HeaderField { name: name.to_string(), value: value.to_string() }
+  }
+
+  /// minimal QPACK decoder, only supporting what the encoder generates.
+  #[derive(Default)]
+  pub(crate) struct Decoder {
+    dynamic_table: Vec<HeaderField>,
+  }
+
+  impl Decoder {
+    pub(crate) fn process_encoder_stream(&mut self, input: &[u8]) {
+      let mut position = 0;
+      while position < input.len() {
+        let octet = input[position];
+        let field = if octet & 0x80 != 0 {
+          let is_static = octet & 0x40 != 0;
+          let index = decode_integer(input, &mut position, 6);
+          let name = if is_static {
+            static_field(index).name
+          } else {
+            self.dynamic_table[self.dynamic_table.len() - 1 - index].name.clone()
+          };
+          HeaderField { name, value: decode_string(input, &mut position, 7) }
+        } else if octet & 0x40 != 0 {
+          let name = decode_string(input, &mut position, 5);
+          HeaderField { name, value: decode_string(input, &mut position, 7) }
+        } else if octet & 0x20 != 0 {
+          let capacity = decode_integer(input, &mut position, 5);
+          assert!(capacity <= MAX_TABLE_CAPACITY, "capacity {capacity} exceeds maximum");
+          continue;
+        } else {
+          let index = decode_integer(input, &mut position, 5);
+          self.dynamic_table[self.dynamic_table.len() - 1 - index].clone()
+        };
+        self.dynamic_table.push(field);
+      }
+    }
+
+    pub(crate) fn decode_field_section(&self, input: &[u8]) -> Vec<HeaderField> {
+      let mut position = 0;
+      let max_entries = MAX_TABLE_CAPACITY / ENTRY_OVERHEAD;
+      let full_range = 2 * max_entries;
+      let encoded_insert_count = decode_integer(input, &mut position, 8);
+      let required_insert_count = if encoded_insert_count == 0 {
+        0
+      } else {
+        let max_value = self.dynamic_table.len() + max_entries;
+        let max_wrapped = (max_value / full_range) * full_range;
+        let mut required_insert_count = max_wrapped + encoded_insert_count - 1;
+        if required_insert_count > max_value {
+          required_insert_count -= full_range;
+        }
+        required_insert_count
+      };
+      assert!(required_insert_count <= self.dynamic_table.len(), "field section is blocked");
+
+      let sign = input[position] & 0x80 != 0;
+      let delta_base = decode_integer(input, &mut position, 7);
+      let base = if sign {
+        required_insert_count - delta_base - 1
+      } else {
+        required_insert_count + delta_base
+      };
+
+      let mut fields = vec![];
+      while position < input.len() {
+        let octet = input[position];
+        let field = if octet & 0x80 != 0 {
+          let is_static = octet & 0x40 != 0;
+          let index = decode_integer(input, &mut position, 6);
+          if is_static { static_field(index) } else { self.dynamic_table[base - 1 - index].clone() }
+        } else if octet & 0x40 != 0 {
+          let is_static = octet & 0x10 != 0;
+          let index = decode_integer(input, &mut position, 4);
+          let name = if is_static {
+            static_field(index).name
+          } else {
+            self.dynamic_table[base - 1 - index].name.clone()
+          };
+          HeaderField { name, value: decode_string(input, &mut position, 7) }
+        } else if octet & 0x20 != 0 {
+          let name = decode_string(input, &mut position, 3);
+          HeaderField { name, value: decode_string(input, &mut position, 7) }
+        } else if octet & 0x10 != 0 {
+          let index = decode_integer(input, &mut position, 4);
+          self.dynamic_table[base + index].clone()
+        } else {
+          let index = decode_integer(input, &mut position, 3);
+          let name = self.dynamic_table[base + index].name.clone();
+          HeaderField { name, value: decode_string(input, &mut position, 7) }
+        };
+        fields.push(field);
+      }
+      fields
+    }
+  }
+
+  fn field(name: &str, value: &str) -> HeaderField {
+    HeaderField { name: name.to_string(), value: value.to_string() }
+  }
+
+  #[test]
+  fn decoder_works() {
+    let mut decoder = Decoder::default();
+    // RFC 9204 appendix B.1
+    assert_eq!(
+      decoder.decode_field_section(&[b"\x00\x00\x51\x0b".as_slice(), b"/index.html"].concat()),
+      [field(":path", "/index.html")]
+    );
+
+    // RFC 9204 appendix B.2
+    decoder.process_encoder_stream(
+      &[b"\x3f\xbd\x01\xc0\x0f".as_slice(), b"www.example.com", b"\xc1\x0c", b"/sample/path"]
+        .concat(),
+    );
+    let dynamic_fields = [field(":authority", "www.example.com"), field(":path", "/sample/path")];
+    assert_eq!(decoder.decode_field_section(b"\x03\x81\x10\x11"), dynamic_fields);
+
+    // RFC 9204 appendix B.3
+    decoder.process_encoder_stream(b"\x4acustom-key\x0ccustom-value");
+    // RFC 9204 appendix B.4
+    decoder.process_encoder_stream(b"\x02");
+    assert_eq!(
+      decoder.decode_field_section(b"\x05\x00\x80\xc1\x81"),
+      [
+        field(":authority", "www.example.com"),
+        field(":path", "/"),
+        field("custom-key", "custom-value")
+      ]
+    );
+
+    // RFC 9204 appendix B.5, the evicted entry is not referenced.
+    decoder.process_encoder_stream(b"\x81\x0dcustom-value2");
+    assert_eq!(decoder.dynamic_table[4], field("custom-key", "custom-value2"));
+  }
+
+  #[test]
+  fn encoder_works() {
+    let choice = FieldChoice {
+      insert: false,
+      duplicate: false,
+      indexed: true,
+      name_reference: true,
+      never_indexed: false,
+      huffman_name: false,
+      huffman_value: false,
+    };
+    // base index of a field section whose fields are all inserted, the base is then 0.
+    let mut runner = proptest::test_runner::TestRunner::deterministic();
+    let base = loop {
+      let base = any::<Index>().new_tree(&mut runner).unwrap().current();
+      if base.index(3) == 0 {
+        break base;
+      }
+    };
+
+    // RFC 9204 appendix B.1
+    let mut encoder = Encoder { dynamic_table: vec![], size: 0, capacity: 220 };
+    let (_, encoded) = encoder.encode(
+      vec![field(":path", "/index.html")],
+      std::slice::from_ref(&choice),
+      base,
+      vec![],
+      vec![],
+    );
+    assert!(encoded.encoder_stream.is_empty());
+    assert_eq!(encoded.field_section, [b"\x00\x00\x51\x0b".as_slice(), b"/index.html"].concat());
+
+    // RFC 9204 appendix B.2
+    let mut encoder_stream = vec![];
+    encode_integer(220, 5, 0x20, &mut encoder_stream);
+    let (section, encoded) = encoder.encode(
+      vec![field(":authority", "www.example.com"), field(":path", "/sample/path")],
+      &[FieldChoice { insert: true, ..choice.clone() }, FieldChoice { insert: true, ..choice }],
+      base,
+      vec![],
+      encoder_stream,
+    );
+    assert_eq!(
+      encoded.encoder_stream,
+      [b"\x3f\xbd\x01\xc0\x0f".as_slice(), b"www.example.com", b"\xc1\x0c", b"/sample/path",]
+        .concat()
+    );
+    assert_eq!((section.required_insert_count, section.base), (2, 0));
+    assert_eq!(encoded.field_section, b"\x03\x81\x10\x11");
+  }
+
+  proptest! {
+    #[test]
+    fn field_section_works((section, encoded) in field_section(0..=20)) {
+      let mut decoder = Decoder::default();
+      decoder.process_encoder_stream(&encoded.encoder_stream);
+      assert_eq!(decoder.decode_field_section(&encoded.field_section), section.fields);
+      assert_eq!(section.representations.len(), section.fields.len());
+    }
+
+    #[test]
+    fn field_sections_works((sections, encoded) in field_sections(1..=5, 0..=20)) {
+      let mut decoder = Decoder::default();
+      for (section, encoded) in sections.iter().zip(encoded) {
+        decoder.process_encoder_stream(&encoded.encoder_stream);
+        assert_eq!(decoder.decode_field_section(&encoded.field_section), section.fields);
+      }
+    }
+  }
+}
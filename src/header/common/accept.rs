@@ -0,0 +1,150 @@
+//! `Accept` header field (RFC 9110 section 12.5.1) strategies.
+
+use proptest::{
+  collection::vec,
+  option::of,
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+use crate::header::common::{
+  any_case,
+  content_type::{MediaType, media_type, parameter},
+  list,
+};
+
+/// `Accept` header field name.
+pub const ACCEPT: &str = "accept";
+
+/// media range with its weight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaRange {
+  /// media type, type and subtype may be `*`.
+  pub media_type: MediaType,
+  /// weight in thousandths, `None` when omitted which means `1`.
+  pub weight: Option<u16>,
+}
+
+/// strategy for generating `qvalue`.
+///
+/// > `qvalue = ( "0" [ "." 0*3DIGIT ] ) / ( "1" [ "." 0*3("0") ] )`
+///
+/// # Returns
+/// weight in thousandths and its representation.
+pub fn qvalue() -> impl Strategy<Value = (u16, String)> {
+  prop_oneof![Just(1000u16), Just(0u16), 0..=1000u16,]
+    .prop_flat_map(|weight| {
+      // minimum number of decimals to represent the weight.
+      let min_decimals = match weight {
+        1000 | 0 => 0,
+        _ if weight % 100 == 0 => 1,
+        _ if weight % 10 == 0 => 2,
+        _ => 3,
+      };
+      (Just(weight), min_decimals..=3usize, proptest::bool::ANY)
+    })
+    .prop_map(|(weight, decimals, dot)| {
+      let integer = weight / 1000;
+      let repr = if decimals == 0 && !dot {
+        integer.to_string()
+      } else {
+        let fraction = format!("{:03}", weight % 1000);
+        format!("{integer}.{}", &fraction[..decimals])
+      };
+      (weight, repr)
+    })
+}
+
+/// strategy for generating media range, with `*/*` and `type/*` wildcards.
+///
+/// # Returns
+/// [`MediaType`] and its representation.
+fn media_range_type() -> impl Strategy<Value = (MediaType, String)> {
+  prop_oneof![
+    3 => media_type().prop_filter("q parameter", |(media_type, _)| {
+      media_type.parameter("q").is_none()
+    }),
+    1 => Just((
+      MediaType { type_: "*".to_string(), subtype: "*".to_string(), parameters: vec![] },
+      "*/*".to_string(),
+    )),
+    1 => (any_case("text"), vec(parameter(), 0..=1))
+      .prop_filter("q parameter", |(_, parameters)| {
+        parameters.iter().all(|(name, _, _)| name != "q")
+      })
+      .prop_map(|(type_repr, parameters)| {
+        let mut repr = format!("{type_repr}/*");
+        let parameters = parameters
+          .into_iter()
+          .map(|(name, value, parameter_repr)| {
+            repr.push_str(&parameter_repr);
+            (name, value)
+          })
+          .collect();
+        (MediaType { type_: "text".to_string(), subtype: "*".to_string(), parameters }, repr)
+      }),
+  ]
+}
+
+/// strategy for generating media range with an optional weight.
+///
+/// > `Accept = #( media-range [ weight ] )`
+/// > `weight = OWS ";" OWS "q=" qvalue`
+///
+/// # Returns
+/// [`MediaRange`] and its representation.
+pub fn media_range() -> impl Strategy<Value = (MediaRange, String)> {
+  (media_range_type(), of((any_case("q"), qvalue()))).prop_map(
+    |((media_type, mut repr), weight)| {
+      let weight = weight.map(|(q, (weight, weight_repr))| {
+        repr.push_str(&format!(";{q}={weight_repr}"));
+        weight
+      });
+      (MediaRange { media_type, weight }, repr)
+    },
+  )
+}
+
+/// strategy for generating `Accept` header field value.
+///
+/// # Returns
+/// media ranges and their representation.
+pub fn accept() -> impl Strategy<Value = (Vec<MediaRange>, String)> {
+  list(media_range(), 1..=5)
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::common::{content_type::tests::media_type_asserts, tests::split_list};
+
+  fn parse_qvalue(repr: &str) -> u16 {
+    let (integer, fraction) = repr.split_once('.').unwrap_or((repr, ""));
+    assert!(fraction.len() <= 3, "too many decimals in {repr:?}");
+    let weight =
+      integer.parse::<u16>().unwrap() * 1000 + format!("{fraction:0<3}").parse::<u16>().unwrap();
+    assert!(weight <= 1000, "weight {repr:?} out of range");
+    weight
+  }
+
+  proptest! {
+    #[test]
+    fn accept_works((ranges, repr) in accept()) {
+      let items = split_list(&repr, ',');
+      assert_eq!(items.len(), ranges.len());
+      for (item, range) in items.into_iter().zip(&ranges) {
+        let last = *split_list(item, ';').last().unwrap();
+        let (media_type, weight) = if last.to_ascii_lowercase().starts_with("q=") {
+          let media_type = item[..item.len() - last.len()].trim_end_matches([' ', '\t', ';']);
+          (media_type, Some(parse_qvalue(&last[2..])))
+        } else {
+          (item, None)
+        };
+        assert_eq!(weight, range.weight);
+        media_type_asserts(&range.media_type, media_type);
+      }
+    }
+  }
+}
@@ -0,0 +1,168 @@
+//! `Authorization` header field (RFC 9110 section 11.6.2) strategies.
+
+use proptest::{prelude::Strategy, prop_oneof};
+
+use crate::header::common::{any_case, list, parameter_value, token};
+
+/// `Authorization` header field name.
+pub const AUTHORIZATION: &str = "authorization";
+
+const BASE64_ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// authentication credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+  /// `Basic` scheme (RFC 7617).
+  Basic { user_id: String, password: String },
+  /// `Bearer` scheme (RFC 6750).
+  Bearer { token: String },
+  /// other scheme with auth parameters, like `Digest`.
+  Params {
+    /// scheme, lower cased.
+    scheme: String,
+    /// parameter names, lower cased, and unquoted values.
+    params: Vec<(String, String)>,
+  },
+}
+
+/// encode octets with the base64 alphabet and padding (RFC 4648 section 4).
+pub fn base64(input: &[u8]) -> String {
+  let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+  for chunk in input.chunks(3) {
+    let bits = chunk
+      .iter()
+      .enumerate()
+      .fold(0u32, |bits, (i, octet)| bits | u32::from(*octet) << (16 - 8 * i));
+    for i in 0..4 {
+      if i <= chunk.len() {
+        output.push(char::from(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize]));
+      } else {
+        output.push('=');
+      }
+    }
+  }
+  output
+}
+
+/// strategy for generating `Basic` credentials.
+///
+/// > `credentials = "Basic" 1*SP token68`
+///
+/// the token is the base64 encoding of `user-id ":" password`, the user id has no colon.
+fn basic() -> impl Strategy<Value = (Credentials, String)> {
+  (any_case("basic"), "[^:\\x00-\\x1f\\x7f]{0,16}", "[^\\x00-\\x1f\\x7f]{0,16}").prop_map(
+    |(scheme, user_id, password)| {
+      let repr = format!("{scheme} {}", base64(format!("{user_id}:{password}").as_bytes()));
+      (Credentials::Basic { user_id, password }, repr)
+    },
+  )
+}
+
+/// strategy for generating `Bearer` credentials.
+///
+/// > `credentials = "Bearer" 1*SP b64token`
+/// > `b64token = 1*( ALPHA / DIGIT / "-" / "." / "_" / "~" / "+" / "/" ) *"="`
+fn bearer() -> impl Strategy<Value = (Credentials, String)> {
+  (any_case("bearer"), "[A-Za-z0-9\\-._~+/]{1,32}={0,2}").prop_map(|(scheme, token)| {
+    let repr = format!("{scheme} {token}");
+    (Credentials::Bearer { token }, repr)
+  })
+}
+
+/// strategy for generating credentials with auth parameters.
+///
+/// > `credentials = auth-scheme [ 1*SP ( token68 / #auth-param ) ]`
+/// > `auth-param = token BWS "=" BWS ( token / quoted-string )`
+fn params() -> impl Strategy<Value = (Credentials, String)> {
+  (
+    prop_oneof![
+      any_case("digest").prop_map(|repr| ("digest".to_string(), repr)),
+      token()
+        .prop_filter("registered scheme", |scheme| {
+          !matches!(scheme.to_ascii_lowercase().as_str(), "basic" | "bearer")
+        })
+        .prop_map(|scheme| (scheme.to_ascii_lowercase(), scheme)),
+    ],
+    list(
+      (token(), "[ \t]{0,1}", "[ \t]{0,1}", parameter_value()).prop_map(
+        |(name, before, after, (value, value_repr))| {
+          let repr = format!("{name}{before}={after}{value_repr}");
+          ((name.to_ascii_lowercase(), value), repr)
+        },
+      ),
+      1..=5,
+    ),
+  )
+    .prop_map(|((scheme, scheme_repr), (params, params_repr))| {
+      (Credentials::Params { scheme, params }, format!("{scheme_repr} {params_repr}"))
+    })
+}
+
+/// strategy for generating `Authorization` header field value.
+///
+/// > `Authorization = credentials`
+///
+/// # Returns
+/// [`Credentials`] and their representation.
+pub fn authorization() -> impl Strategy<Value = (Credentials, String)> {
+  prop_oneof![basic(), bearer(), params()]
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::common::tests::{split_list, unquote};
+
+  fn base64_decode(input: &str) -> Vec<u8> {
+    let input = input.trim_end_matches('=');
+    let bits = input
+      .bytes()
+      .map(|c| BASE64_ALPHABET.iter().position(|a| *a == c).unwrap() as u32)
+      .collect::<Vec<_>>();
+    let mut output = vec![];
+    for chunk in bits.chunks(4) {
+      let value =
+        chunk.iter().enumerate().fold(0u32, |value, (i, bits)| value | bits << (18 - 6 * i));
+      output.extend(&value.to_be_bytes()[1..chunk.len()]);
+    }
+    output
+  }
+
+  #[test]
+  fn base64_works() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"Aladdin:open sesame"), "QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+  }
+
+  proptest! {
+    #[test]
+    fn authorization_works((credentials, repr) in authorization()) {
+      let (scheme, rest) = repr.split_once(' ').unwrap();
+      let parsed = match scheme.to_ascii_lowercase().as_str() {
+        "basic" => {
+          let decoded = String::from_utf8(base64_decode(rest)).unwrap();
+          let (user_id, password) = decoded.split_once(':').unwrap();
+          Credentials::Basic { user_id: user_id.to_string(), password: password.to_string() }
+        }
+        "bearer" => Credentials::Bearer { token: rest.to_string() },
+        scheme => Credentials::Params {
+          scheme: scheme.to_string(),
+          params: split_list(rest, ',')
+            .into_iter()
+            .map(|param| {
+              let (name, value) = param.split_once('=').unwrap();
+              (name.trim_end().to_ascii_lowercase(), unquote(value.trim_start()))
+            })
+            .collect(),
+        },
+      };
+      assert_eq!(parsed, credentials);
+    }
+  }
+}
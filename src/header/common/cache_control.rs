@@ -0,0 +1,128 @@
+//! `Cache-Control` header field (RFC 9111 section 5.2) strategies.
+
+use proptest::{
+  option::of,
+  prelude::{Strategy, any},
+  prop_oneof,
+};
+
+use crate::header::common::{any_case, list, parameter_value, quote, token};
+
+/// `Cache-Control` header field name.
+pub const CACHE_CONTROL: &str = "cache-control";
+
+/// request cache directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+  MaxAge(u32),
+  /// `max-stale` with an optional number of seconds, without it any stale response is accepted.
+  MaxStale(Option<u32>),
+  MinFresh(u32),
+  NoCache,
+  NoStore,
+  NoTransform,
+  OnlyIfCached,
+  /// cache extension with lower cased name and unquoted argument.
+  Extension(String, Option<String>),
+}
+
+/// strategy for generating `delta-seconds`, randomly quoted.
+///
+/// > `delta-seconds = 1*DIGIT`
+fn delta_seconds() -> impl Strategy<Value = (u32, String)> {
+  (any::<u32>(), proptest::bool::weighted(0.2)).prop_map(|(seconds, quoted)| {
+    let repr = if quoted { quote(&seconds.to_string()) } else { seconds.to_string() };
+    (seconds, repr)
+  })
+}
+
+/// strategy for generating request cache directive.
+///
+/// > `cache-directive = token [ "=" ( token / quoted-string ) ]`
+///
+/// # Returns
+/// [`CacheDirective`] and its representation.
+pub fn cache_directive() -> impl Strategy<Value = (CacheDirective, String)> {
+  prop_oneof![
+    (any_case("max-age"), delta_seconds()).prop_map(|(name, (seconds, repr))| {
+      (CacheDirective::MaxAge(seconds), format!("{name}={repr}"))
+    }),
+    (any_case("max-stale"), of(delta_seconds())).prop_map(|(name, seconds)| match seconds {
+      Some((seconds, repr)) => (CacheDirective::MaxStale(Some(seconds)), format!("{name}={repr}")),
+      None => (CacheDirective::MaxStale(None), name),
+    }),
+    (any_case("min-fresh"), delta_seconds()).prop_map(|(name, (seconds, repr))| {
+      (CacheDirective::MinFresh(seconds), format!("{name}={repr}"))
+    }),
+    any_case("no-cache").prop_map(|name| (CacheDirective::NoCache, name)),
+    any_case("no-store").prop_map(|name| (CacheDirective::NoStore, name)),
+    any_case("no-transform").prop_map(|name| (CacheDirective::NoTransform, name)),
+    any_case("only-if-cached").prop_map(|name| (CacheDirective::OnlyIfCached, name)),
+    (token(), of(parameter_value()))
+      .prop_filter("registered directive", |(name, _)| {
+        !matches!(
+          name.to_ascii_lowercase().as_str(),
+          "max-age"
+            | "max-stale"
+            | "min-fresh"
+            | "no-cache"
+            | "no-store"
+            | "no-transform"
+            | "only-if-cached"
+        )
+      })
+      .prop_map(|(name, argument)| {
+        let lower = name.to_ascii_lowercase();
+        match argument {
+          Some((value, repr)) => {
+            (CacheDirective::Extension(lower, Some(value)), format!("{name}={repr}"))
+          }
+          None => (CacheDirective::Extension(lower, None), name),
+        }
+      }),
+  ]
+}
+
+/// strategy for generating `Cache-Control` header field value of a request.
+///
+/// > `Cache-Control = #cache-directive`
+///
+/// # Returns
+/// directives and their representation.
+pub fn cache_control() -> impl Strategy<Value = (Vec<CacheDirective>, String)> {
+  list(cache_directive(), 1..=5)
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::common::tests::{split_list, unquote};
+
+  fn parse_directive(repr: &str) -> CacheDirective {
+    let (name, argument) = match repr.split_once('=') {
+      Some((name, argument)) => (name.to_ascii_lowercase(), Some(unquote(argument))),
+      None => (repr.to_ascii_lowercase(), None),
+    };
+    let seconds = |argument: Option<String>| argument.unwrap().parse::<u32>().unwrap();
+    match name.as_str() {
+      "max-age" => CacheDirective::MaxAge(seconds(argument)),
+      "max-stale" => CacheDirective::MaxStale(argument.map(|argument| seconds(Some(argument)))),
+      "min-fresh" => CacheDirective::MinFresh(seconds(argument)),
+      "no-cache" => CacheDirective::NoCache,
+      "no-store" => CacheDirective::NoStore,
+      "no-transform" => CacheDirective::NoTransform,
+      "only-if-cached" => CacheDirective::OnlyIfCached,
+      _ => CacheDirective::Extension(name, argument),
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn cache_control_works((directives, repr) in cache_control()) {
+      let parsed = split_list(&repr, ',').into_iter().map(parse_directive).collect::<Vec<_>>();
+      assert_eq!(parsed, directives);
+    }
+  }
+}
@@ -0,0 +1,132 @@
+//! `Content-Type` header field (RFC 9110 section 8.3) strategies.
+
+use proptest::{collection::vec, prelude::Strategy, prop_oneof, sample::select};
+
+use crate::header::common::{any_case, parameter_value, token};
+
+/// `Content-Type` header field name.
+pub const CONTENT_TYPE: &str = "content-type";
+
+/// frequently used media types.
+const MEDIA_TYPES: &[(&str, &str)] = &[
+  ("text", "plain"),
+  ("text", "html"),
+  ("text", "css"),
+  ("text", "csv"),
+  ("application", "json"),
+  ("application", "xml"),
+  ("application", "octet-stream"),
+  ("application", "x-www-form-urlencoded"),
+  ("application", "problem+json"),
+  ("multipart", "form-data"),
+  ("multipart", "byteranges"),
+  ("image", "png"),
+  ("image", "svg+xml"),
+];
+
+/// media type with its parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+  /// type, lower cased.
+  pub type_: String,
+  /// subtype, lower cased.
+  pub subtype: String,
+  /// parameter names, lower cased, and unquoted values.
+  pub parameters: Vec<(String, String)>,
+}
+
+impl MediaType {
+  /// value of the first parameter with the given lower cased name.
+  pub fn parameter(&self, name: &str) -> Option<&str> {
+    self.parameters.iter().find(|(n, _)| n == name).map(|(_, value)| value.as_str())
+  }
+}
+
+/// strategy for generating a type or subtype, usually the given one in random case.
+fn type_name(name: &'static str) -> impl Strategy<Value = (String, String)> {
+  prop_oneof![
+    3 => any_case(name).prop_map(move |repr| (name.to_string(), repr)),
+    1 => token().prop_map(|name| (name.to_ascii_lowercase(), name)),
+  ]
+}
+
+/// strategy for generating media type parameter.
+///
+/// # Returns
+/// lower cased name, unquoted value and the parameter representation, with its leading `;`.
+pub fn parameter() -> impl Strategy<Value = (String, String, String)> {
+  (
+    prop_oneof![
+      2 => any_case("charset").prop_map(|name| ("charset".to_string(), name)),
+      1 => token().prop_map(|name| (name.to_ascii_lowercase(), name)),
+    ],
+    "[ \t]{0,2}",
+    "[ \t]{0,2}",
+    parameter_value(),
+  )
+    .prop_map(|((name, name_repr), before, after, (value, value_repr))| {
+      (name, value, format!("{before};{after}{name_repr}={value_repr}"))
+    })
+}
+
+/// strategy for generating media type with parameters.
+///
+/// > `media-type = type "/" subtype parameters`
+/// > `parameters = *( OWS ";" OWS [ parameter ] )`
+///
+/// # Returns
+/// [`MediaType`] and its representation.
+pub fn media_type() -> impl Strategy<Value = (MediaType, String)> {
+  select(MEDIA_TYPES)
+    .prop_flat_map(|(type_, subtype)| {
+      (type_name(type_), type_name(subtype), vec(parameter(), 0..=3))
+    })
+    .prop_map(|((type_, type_repr), (subtype, subtype_repr), parameters)| {
+      let mut repr = format!("{type_repr}/{subtype_repr}");
+      let parameters = parameters
+        .into_iter()
+        .map(|(name, value, parameter_repr)| {
+          repr.push_str(&parameter_repr);
+          (name, value)
+        })
+        .collect();
+      (MediaType { type_, subtype, parameters }, repr)
+    })
+}
+
+/// strategy for generating `Content-Type` header field value.
+///
+/// see [`media_type`].
+pub fn content_type() -> impl Strategy<Value = (MediaType, String)> {
+  media_type()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::common::tests::{split_list, unquote};
+
+  pub(crate) fn media_type_asserts(media_type: &MediaType, repr: &str) {
+    let mut parts = split_list(repr, ';').into_iter();
+    let (type_, subtype) = parts.next().unwrap().split_once('/').unwrap();
+    assert_eq!(type_.to_ascii_lowercase(), media_type.type_);
+    assert_eq!(subtype.to_ascii_lowercase(), media_type.subtype);
+
+    let parameters = parts
+      .map(|parameter| {
+        let (name, value) = parameter.split_once('=').unwrap();
+        (name.to_ascii_lowercase(), unquote(value))
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(parameters, media_type.parameters);
+  }
+
+  proptest! {
+    #[test]
+    fn media_type_works((media_type, repr) in media_type()) {
+      media_type_asserts(&media_type, &repr);
+    }
+  }
+}
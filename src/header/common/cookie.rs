@@ -0,0 +1,286 @@
+//! `Cookie` and `Set-Cookie` header fields (RFC 6265) strategies.
+
+use std::num::NonZero;
+
+use proptest::{
+  collection::vec,
+  option::of,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+};
+
+use crate::{
+  header::common::{
+    any_case,
+    date::{HttpDate, http_date},
+    token,
+  },
+  request_line::target::components::{host::domain::domain, path::path_absolute},
+};
+
+/// `Cookie` header field name.
+pub const COOKIE: &str = "cookie";
+/// `Set-Cookie` header field name.
+pub const SET_COOKIE: &str = "set-cookie";
+
+/// cookie name and value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookiePair {
+  pub name: String,
+  /// value, double quotes included when quoted, as they are part of the value.
+  pub value: String,
+}
+
+/// `SameSite` attribute value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+  Strict,
+  Lax,
+  None,
+}
+
+/// cookie expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expiry {
+  /// `Max-Age` in seconds, the cookie expires immediately when not positive.
+  MaxAge(i64),
+  /// `Expires` date.
+  Expires(HttpDate),
+}
+
+/// cookie as stored in a cookie jar after parsing `Set-Cookie` (RFC 6265 section 5.2).
+///
+/// cookie jars reject `SameSite=None` and `Partitioned` cookies without `Secure`
+/// (RFC 6265bis section 5.7, CHIPS), so those cookies are always secure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookie {
+  pub cookie: CookiePair,
+  /// expiry, `Max-Age` taking precedence over `Expires`, `None` for session cookies.
+  pub expiry: Option<Expiry>,
+  /// domain, lower cased without leading dot, `None` for host-only cookies.
+  pub domain: Option<String>,
+  /// path, `None` for the default path of the request URI.
+  pub path: Option<String>,
+  pub secure: bool,
+  pub http_only: bool,
+  pub same_site: Option<SameSite>,
+  pub partitioned: bool,
+}
+
+/// strategy for generating cookie pair.
+///
+/// > `cookie-pair = cookie-name "=" cookie-value`
+/// > `cookie-value = *cookie-octet / ( DQUOTE *cookie-octet DQUOTE )`
+/// > `cookie-octet = %x21 / %x23-2B / %x2D-3A / %x3C-5B / %x5D-7E`
+///
+/// # Returns
+/// [`CookiePair`] and its representation.
+pub fn cookie_pair() -> impl Strategy<Value = (CookiePair, String)> {
+  (token(), "[\\x21\\x23-\\x2B\\x2D-\\x3A\\x3C-\\x5B\\x5D-\\x7E]{0,16}", any::<bool>()).prop_map(
+    |(name, value, quoted)| {
+      let value = if quoted { format!("\"{value}\"") } else { value };
+      let repr = format!("{name}={value}");
+      (CookiePair { name, value }, repr)
+    },
+  )
+}
+
+/// strategy for generating `Cookie` header field value.
+///
+/// > `cookie-string = cookie-pair *( ";" SP cookie-pair )`
+///
+/// # Returns
+/// cookie pairs and their representation.
+pub fn cookie() -> impl Strategy<Value = (Vec<CookiePair>, String)> {
+  vec(cookie_pair(), 1..=8).prop_map(|pairs| {
+    let (pairs, reprs): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+    (pairs, reprs.join("; "))
+  })
+}
+
+fn same_site() -> impl Strategy<Value = (SameSite, String)> {
+  prop_oneof![
+    any_case("strict").prop_map(|repr| (SameSite::Strict, repr)),
+    any_case("lax").prop_map(|repr| (SameSite::Lax, repr)),
+    any_case("none").prop_map(|repr| (SameSite::None, repr)),
+  ]
+}
+
+/// strategy for generating `Set-Cookie` header field value.
+///
+/// > `set-cookie-string = cookie-pair *( ";" SP cookie-av )`
+///
+/// attributes are in random order with names in random case, each at most once. `Secure` is
+/// always present along with `SameSite=None` or `Partitioned`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count of the `Domain` attribute.
+/// * `max_segments`: maximum number of segments of the `Path` attribute.
+///
+/// # Returns
+/// [`SetCookie`] and its representation.
+pub fn set_cookie(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+) -> impl Strategy<Value = (SetCookie, String)> {
+  (
+    cookie_pair(),
+    (
+      of((any_case("expires"), http_date())),
+      of((any_case("max-age"), prop_oneof![-1000..=0i64, 1..=i64::from(i32::MAX)])),
+      of((any_case("domain"), domain(max_label_count))),
+      of((
+        any_case("path"),
+        path_absolute(max_segments)
+          .prop_filter("semicolon in path", |(_, repr)| !repr.contains(';')),
+      )),
+    ),
+    (
+      (any_case("secure"), any::<bool>()),
+      of(any_case("httponly")),
+      of((any_case("samesite"), same_site())),
+      of(any_case("partitioned")),
+    ),
+  )
+    .prop_flat_map(
+      |(
+        (cookie, cookie_repr),
+        (expires, max_age, domain, path),
+        (secure, http_only, same_site, partitioned),
+      )| {
+        let mut attributes = vec![];
+        if let Some((name, (_, repr))) = &expires {
+          attributes.push(format!("{name}={repr}"));
+        }
+        if let Some((name, max_age)) = &max_age {
+          attributes.push(format!("{name}={max_age}"));
+        }
+        if let Some((name, domain)) = &domain {
+          attributes.push(format!("{name}={domain}"));
+        }
+        if let Some((name, (_, repr))) = &path {
+          attributes.push(format!("{name}={repr}"));
+        }
+        let (secure, secure_flag) = secure;
+        let secure = (secure_flag
+          || partitioned.is_some()
+          || matches!(same_site, Some((_, (SameSite::None, _)))))
+        .then_some(secure);
+        attributes.extend(secure.iter().chain(&http_only).chain(&partitioned).cloned());
+        if let Some((name, (_, repr))) = &same_site {
+          attributes.push(format!("{name}={repr}"));
+        }
+
+        let set_cookie = SetCookie {
+          cookie,
+          expiry: match (max_age, expires) {
+            (Some((_, max_age)), _) => Some(Expiry::MaxAge(max_age)),
+            (None, Some((_, (date, _)))) => Some(Expiry::Expires(date)),
+            (None, None) => None,
+          },
+          domain: domain
+            .map(|(_, domain)| domain.strip_prefix('.').unwrap_or(&domain).to_ascii_lowercase()),
+          path: path.map(|(_, (_, repr))| repr),
+          secure: secure.is_some(),
+          http_only: http_only.is_some(),
+          same_site: same_site.map(|(_, (same_site, _))| same_site),
+          partitioned: partitioned.is_some(),
+        };
+        (Just((set_cookie, cookie_repr)), Just(attributes).prop_shuffle())
+      },
+    )
+    .prop_map(|((set_cookie, mut repr), attributes)| {
+      for attribute in attributes {
+        repr.push_str("; ");
+        repr.push_str(&attribute);
+      }
+      (set_cookie, repr)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  /// parse a cookie pair following RFC 6265 section 5.2.
+  fn parse_pair(repr: &str) -> CookiePair {
+    let (name, value) = repr.split_once('=').unwrap();
+    CookiePair { name: name.trim().to_string(), value: value.trim().to_string() }
+  }
+
+  /// parse a `Set-Cookie` header field value following RFC 6265 section 5.2.
+  ///
+  /// # Returns
+  /// the cookie, without expiry, and the `Max-Age` and `Expires` attribute values.
+  fn parse_set_cookie(repr: &str) -> (SetCookie, Option<i64>, Option<&str>) {
+    let mut parts = repr.split(';');
+    let mut set_cookie = SetCookie {
+      cookie: parse_pair(parts.next().unwrap()),
+      expiry: None,
+      domain: None,
+      path: None,
+      secure: false,
+      http_only: false,
+      same_site: None,
+      partitioned: false,
+    };
+    let (mut max_age, mut expires) = (None, None);
+    for attribute in parts {
+      let (name, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+      let value = value.trim();
+      match name.trim().to_ascii_lowercase().as_str() {
+        "expires" => expires = Some(value),
+        "max-age" => max_age = Some(value.parse::<i64>().unwrap()),
+        "domain" => {
+          set_cookie.domain = Some(value.strip_prefix('.').unwrap_or(value).to_ascii_lowercase());
+        }
+        "path" => {
+          assert!(value.starts_with('/'), "path {value:?} should be absolute");
+          set_cookie.path = Some(value.to_string());
+        }
+        "secure" => set_cookie.secure = true,
+        "httponly" => set_cookie.http_only = true,
+        "partitioned" => set_cookie.partitioned = true,
+        "samesite" => {
+          set_cookie.same_site = Some(match value.to_ascii_lowercase().as_str() {
+            "strict" => SameSite::Strict,
+            "lax" => SameSite::Lax,
+            "none" => SameSite::None,
+            value => panic!("unexpected SameSite {value:?}"),
+          });
+        }
+        name => panic!("unexpected attribute {name:?}"),
+      }
+    }
+    (set_cookie, max_age, expires)
+  }
+
+  proptest! {
+    #[test]
+    fn cookie_works((pairs, repr) in cookie()) {
+      let parsed = repr.split("; ").map(parse_pair).collect::<Vec<_>>();
+      assert_eq!(parsed, pairs);
+    }
+
+    #[test]
+    fn set_cookie_works((set_cookie, repr) in set_cookie(10, 10.try_into().unwrap())) {
+      let (parsed, max_age, expires) = parse_set_cookie(&repr);
+      match (set_cookie.expiry, max_age, expires) {
+        (Some(Expiry::MaxAge(expected)), Some(max_age), _) => assert_eq!(max_age, expected),
+        (Some(Expiry::Expires(date)), None, Some(expires)) => {
+          assert_eq!(expires, date.to_string());
+        }
+        (None, None, None) => {}
+        (expiry, max_age, expires) => {
+          panic!("expected expiry {expiry:?} but got Max-Age {max_age:?} and Expires {expires:?}")
+        }
+      }
+      assert_eq!(SetCookie { expiry: set_cookie.expiry, ..parsed }, set_cookie);
+      if parsed.partitioned || parsed.same_site == Some(SameSite::None) {
+        assert!(parsed.secure, "cookie jars reject {repr:?} without Secure");
+      }
+    }
+  }
+}
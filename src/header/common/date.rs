@@ -0,0 +1,199 @@
+//! `Date` header field (RFC 9110 section 6.6.1) and `HTTP-date` (section 5.6.7) strategies.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use proptest::{
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+/// `Date` header field name.
+pub const DATE: &str = "date";
+
+const DAY_NAMES: [&str; 7] =
+  ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+const MONTH_NAMES: [&str; 12] =
+  ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// seconds since the epoch of `1970-01-01T00:00:00Z`.
+const SECONDS_1970: u64 = 0;
+/// seconds since the epoch of `2000-01-01T00:00:00Z`.
+const SECONDS_2000: u64 = 946_684_800;
+/// seconds since the epoch of `2049-12-31T23:59:59Z`.
+const SECONDS_2049: u64 = 2_524_607_999;
+/// seconds since the epoch of `9999-12-31T23:59:59Z`.
+const SECONDS_9999: u64 = 253_402_300_799;
+
+/// `HTTP-date` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpDateFormat {
+  /// preferred format, `Sun, 06 Nov 1994 08:49:37 GMT`.
+  Imf,
+  /// obsolete RFC 850 format, `Sunday, 06-Nov-94 08:49:37 GMT`.
+  Rfc850,
+  /// obsolete ANSI C's `asctime()` format, `Sun Nov  6 08:49:37 1994`.
+  Asctime,
+}
+
+/// `HTTP-date`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpDate {
+  /// seconds since the Unix epoch.
+  pub seconds: u64,
+  pub format: HttpDateFormat,
+}
+
+impl HttpDate {
+  pub fn system_time(&self) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(self.seconds)
+  }
+}
+
+/// convert days since the Unix epoch to a civil date.
+///
+/// # Returns
+/// year, month in `1..=12` and day in `1..=31`.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+  // shift the epoch to 0000-03-01, so leap days end years, and eras are 400 years long.
+  let days = days + 719_468;
+  let era = days / 146_097;
+  let day_of_era = days % 146_097;
+  let year_of_era =
+    (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+  let year = year_of_era + era * 400 + u64::from(month <= 2);
+  (year, month, day)
+}
+
+impl std::fmt::Display for HttpDate {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let days = self.seconds / 86_400;
+    let (hour, minute, second) =
+      (self.seconds % 86_400 / 3600, self.seconds % 3600 / 60, self.seconds % 60);
+    let (year, month, day) = civil_from_days(days);
+    // 1970-01-01 is a Thursday.
+    let day_name = DAY_NAMES[((days + 3) % 7) as usize];
+    let month_name = MONTH_NAMES[month as usize - 1];
+    match self.format {
+      HttpDateFormat::Imf => write!(
+        f,
+        "{}, {day:02} {month_name} {year} {hour:02}:{minute:02}:{second:02} GMT",
+        &day_name[..3]
+      ),
+      HttpDateFormat::Rfc850 => write!(
+        f,
+        "{day_name}, {day:02}-{month_name}-{:02} {hour:02}:{minute:02}:{second:02} GMT",
+        year % 100
+      ),
+      HttpDateFormat::Asctime => write!(
+        f,
+        "{} {month_name} {day:>2} {hour:02}:{minute:02}:{second:02} {year}",
+        &day_name[..3]
+      ),
+    }
+  }
+}
+
+/// strategy for generating `HTTP-date`.
+///
+/// > `HTTP-date = IMF-fixdate / obs-date`
+///
+/// dates are between 1970 and 9999, and between 2000 and 2049 for the RFC 850 format, whose
+/// two-digit years are ambiguous otherwise.
+///
+/// # Returns
+/// [`HttpDate`] and its representation.
+pub fn http_date() -> impl Strategy<Value = (HttpDate, String)> {
+  prop_oneof![
+    4 => (SECONDS_1970..=SECONDS_9999).prop_map(|seconds| (seconds, HttpDateFormat::Imf)),
+    1 => (SECONDS_2000..=SECONDS_2049).prop_map(|seconds| (seconds, HttpDateFormat::Rfc850)),
+    1 => (SECONDS_1970..=SECONDS_9999).prop_map(|seconds| (seconds, HttpDateFormat::Asctime)),
+    1 => Just((SECONDS_1970, HttpDateFormat::Imf)),
+  ]
+  .prop_map(|(seconds, format)| {
+    let date = HttpDate { seconds, format };
+    (date, date.to_string())
+  })
+}
+
+/// strategy for generating `Date` header field value.
+///
+/// see [`http_date`].
+pub fn date() -> impl Strategy<Value = (HttpDate, String)> {
+  http_date()
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  /// convert a civil date to days since the Unix epoch, by counting.
+  fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let is_leap = |year: u64| year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = [31, 28 + u64::from(is_leap(year)), 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    assert!((1..=month_days[month as usize - 1]).contains(&day));
+    // days of the years before, without leap days, and leap days.
+    let leap_days = |year: u64| year / 4 - year / 100 + year / 400;
+    (year - 1970) * 365 + leap_days(year - 1) - leap_days(1969)
+      + month_days[..month as usize - 1].iter().sum::<u64>()
+      + day
+      - 1
+  }
+
+  fn parse_time(repr: &str) -> u64 {
+    let parts = repr.split(':').map(|part| part.parse::<u64>().unwrap()).collect::<Vec<_>>();
+    parts[0] * 3600 + parts[1] * 60 + parts[2]
+  }
+
+  fn parse_month(repr: &str) -> u64 {
+    MONTH_NAMES.iter().position(|name| *name == repr).unwrap() as u64 + 1
+  }
+
+  #[test]
+  fn http_date_formats_work() {
+    let date = |format| HttpDate { seconds: 784_111_777, format }.to_string();
+    assert_eq!(date(HttpDateFormat::Imf), "Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(date(HttpDateFormat::Rfc850), "Sunday, 06-Nov-94 08:49:37 GMT");
+    assert_eq!(date(HttpDateFormat::Asctime), "Sun Nov  6 08:49:37 1994");
+  }
+
+  proptest! {
+    #[test]
+    fn http_date_works((date, repr) in http_date()) {
+      let (day_name, year, month, day, time) = match date.format {
+        HttpDateFormat::Imf => {
+          let parts = repr.split(' ').collect::<Vec<_>>();
+          assert_eq!(parts.len(), 6);
+          assert_eq!(parts[5], "GMT");
+          assert_eq!(parts[1].len(), 2);
+          let day_name = parts[0].strip_suffix(',').unwrap();
+          (day_name, parts[3].parse().unwrap(), parse_month(parts[2]), parts[1].parse().unwrap(), parts[4])
+        }
+        HttpDateFormat::Rfc850 => {
+          let parts = repr.split(' ').collect::<Vec<_>>();
+          assert_eq!(parts.len(), 4);
+          assert_eq!(parts[3], "GMT");
+          let day_name = parts[0].strip_suffix(',').unwrap();
+          let date = parts[1].split('-').collect::<Vec<_>>();
+          assert_eq!(date[2].len(), 2);
+          (day_name, 2000 + date[2].parse::<u64>().unwrap(), parse_month(date[1]), date[0].parse().unwrap(), parts[2])
+        }
+        HttpDateFormat::Asctime => {
+          let parts = repr.split_whitespace().collect::<Vec<_>>();
+          assert_eq!(parts.len(), 5);
+          assert_eq!(repr.len(), 24);
+          (parts[0], parts[4].parse().unwrap(), parse_month(parts[1]), parts[2].parse().unwrap(), parts[3])
+        }
+      };
+      let days = days_from_civil(year, month, day);
+      assert_eq!(days * 86_400 + parse_time(time), date.seconds);
+      assert!(DAY_NAMES[((days + 3) % 7) as usize].starts_with(day_name));
+      assert_eq!(date.system_time().duration_since(UNIX_EPOCH).unwrap().as_secs(), date.seconds);
+    }
+  }
+}
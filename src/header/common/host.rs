@@ -0,0 +1,111 @@
+//! `Host` header field (RFC 9110 section 7.2) strategies.
+
+use proptest::{
+  option::of,
+  prelude::{Strategy, any},
+};
+
+use crate::request_line::target::{
+  RequestTarget,
+  authority_form::AuthorityForm,
+  components::{
+    authority::Authority,
+    host::{Host, host},
+  },
+};
+
+/// `Host` header field name.
+pub const HOST: &str = "host";
+
+/// `Host` header field value.
+#[derive(Debug, Clone)]
+pub struct HostHeader {
+  pub host: Host,
+  /// port, `None` for the default port of the scheme.
+  pub port: Option<u16>,
+}
+
+impl HostHeader {
+  /// representation of the host.
+  pub fn host_repr(&self) -> &str {
+    match &self.host {
+      Host::Domain(repr) | Host::Ipv6(_, repr) | Host::Ipv4(_, repr) => repr,
+    }
+  }
+
+  /// `Host` header field value required by the request target.
+  ///
+  /// # Returns
+  /// the target authority, without user info, for absolute and authority forms, `None` for
+  /// other forms which carry no authority.
+  pub fn from_target(target: &RequestTarget) -> Option<Self> {
+    match target {
+      RequestTarget::Absolute(absolute) => Some(absolute.authority.clone().into()),
+      RequestTarget::Authority(authority) => Some(authority.clone().into()),
+      RequestTarget::Origin(_) | RequestTarget::Asterisk => None,
+    }
+  }
+}
+
+impl From<AuthorityForm> for HostHeader {
+  fn from(authority: AuthorityForm) -> Self {
+    Self { host: authority.host, port: Some(authority.port) }
+  }
+}
+
+impl From<Authority> for HostHeader {
+  fn from(authority: Authority) -> Self {
+    Self { host: authority.host, port: authority.port }
+  }
+}
+
+impl std::fmt::Display for HostHeader {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.port {
+      Some(port) => write!(f, "{}:{port}", self.host_repr()),
+      None => write!(f, "{}", self.host_repr()),
+    }
+  }
+}
+
+/// strategy for generating `Host` header field value.
+///
+/// > `Host = uri-host [ ":" port ]`
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+///
+/// # Returns
+/// [`HostHeader`] and its representation.
+pub fn host_header(max_label_count: usize) -> impl Strategy<Value = (HostHeader, String)> {
+  (host(max_label_count), of(any::<u16>())).prop_map(|(host, port)| {
+    let header = HostHeader { host, port };
+    let repr = header.to_string();
+    (header, repr)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use claims::assert_ok;
+  use proptest::proptest;
+  use url::Url;
+
+  use super::*;
+
+  proptest! {
+    #[test]
+    fn host_header_works((header, repr) in host_header(20)) {
+      let url = assert_ok!(Url::parse(&format!("http://{repr}/")));
+      match (&header.host, url.host()) {
+        (Host::Domain(domain), Some(url::Host::Domain(parsed))) => {
+          assert_eq!(domain.to_lowercase(), parsed);
+        }
+        (Host::Ipv4(ip, _), Some(url::Host::Ipv4(parsed))) => assert_eq!(*ip, parsed),
+        (Host::Ipv6(ip, _), Some(url::Host::Ipv6(parsed))) => assert_eq!(*ip, parsed),
+        (host, parsed) => panic!("expected host {host:?} but parsed {parsed:?}"),
+      }
+      assert_eq!(url.port_or_known_default(), Some(header.port.unwrap_or(80)));
+    }
+  }
+}
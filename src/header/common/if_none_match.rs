@@ -0,0 +1,97 @@
+//! `If-None-Match` header field (RFC 9110 section 13.1.2) strategies.
+
+use proptest::{
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+use crate::header::common::list;
+
+/// `If-None-Match` header field name.
+pub const IF_NONE_MATCH: &str = "if-none-match";
+
+/// entity tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityTag {
+  /// weak validator, with the `W/` prefix.
+  pub weak: bool,
+  /// opaque tag, without the quotes.
+  pub opaque: String,
+}
+
+impl std::fmt::Display for EntityTag {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.weak {
+      write!(f, "W/")?;
+    }
+    write!(f, "\"{}\"", self.opaque)
+  }
+}
+
+/// `If-None-Match` header field value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatch {
+  /// `*`, matches any current representation.
+  Any,
+  /// list of entity tags.
+  Tags(Vec<EntityTag>),
+}
+
+/// strategy for generating entity tag.
+///
+/// > `entity-tag = [ weak ] opaque-tag`
+/// > `opaque-tag = DQUOTE *etagc DQUOTE`
+/// > `etagc = %x21 / %x23-7E / obs-text`
+pub fn entity_tag() -> impl Strategy<Value = EntityTag> {
+  (proptest::bool::ANY, "[!#-~]{0,16}").prop_map(|(weak, opaque)| EntityTag { weak, opaque })
+}
+
+/// strategy for generating `If-None-Match` header field value.
+///
+/// > `If-None-Match = "*" / #entity-tag`
+///
+/// # Returns
+/// [`IfNoneMatch`] and its representation.
+pub fn if_none_match() -> impl Strategy<Value = (IfNoneMatch, String)> {
+  let tag = entity_tag().prop_map(|tag| {
+    let repr = tag.to_string();
+    (tag, repr)
+  });
+  prop_oneof![
+    1 => Just((IfNoneMatch::Any, "*".to_string())),
+    4 => list(tag, 1..=5).prop_map(|(tags, repr)| (IfNoneMatch::Tags(tags), repr)),
+  ]
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  proptest! {
+    #[test]
+    fn if_none_match_works((if_none_match, repr) in if_none_match()) {
+      // opaque tags have no escapes, unlike quoted strings.
+      let parsed = if repr == "*" {
+        IfNoneMatch::Any
+      } else {
+        let mut tags = vec![];
+        let mut rest = repr.as_str();
+        while !rest.is_empty() {
+          rest = rest.trim_start_matches([' ', '\t', ',']);
+          let (weak, tag) = match rest.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None => (false, rest),
+          };
+          let (opaque, tail) = tag.strip_prefix('"').unwrap().split_once('"').unwrap();
+          tags.push(EntityTag { weak, opaque: opaque.to_string() });
+          rest = tail.trim_start_matches([' ', '\t']);
+          assert!(rest.is_empty() || rest.starts_with(','));
+        }
+        IfNoneMatch::Tags(tags)
+      };
+      assert_eq!(parsed, if_none_match);
+    }
+  }
+}
@@ -0,0 +1,164 @@
+//! typed strategies for commonly used header fields.
+//!
+//! every strategy generates a typed model of the field and the representation of its value.
+
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+pub mod accept;
+pub mod authorization;
+pub mod cache_control;
+pub mod content_type;
+pub mod cookie;
+pub mod date;
+pub mod host;
+pub mod if_none_match;
+pub mod range;
+
+/// strategy for generating `token`.
+///
+/// > `token = 1*tchar`
+pub fn token() -> impl Strategy<Value = String> {
+  "[!#$%&'*+\\-.^_`|~0-9A-Za-z]{1,16}"
+}
+
+/// quote a string, escaping `"` and `\` with a backslash.
+///
+/// > `quoted-string = DQUOTE *( qdtext / quoted-pair ) DQUOTE`
+pub fn quote(value: &str) -> String {
+  let mut quoted = String::with_capacity(value.len() + 2);
+  quoted.push('"');
+  for c in value.chars() {
+    if matches!(c, '"' | '\\') {
+      quoted.push('\\');
+    }
+    quoted.push(c);
+  }
+  quoted.push('"');
+  quoted
+}
+
+/// strategy for generating parameter value, either a `token` or a `quoted-string`.
+///
+/// tokens are randomly quoted, other values are always quoted.
+///
+/// # Returns
+/// unquoted value and its representation.
+pub fn parameter_value() -> impl Strategy<Value = (String, String)> {
+  prop_oneof![
+    (token(), proptest::bool::ANY).prop_map(|(value, quoted)| {
+      let repr = if quoted { quote(&value) } else { value.clone() };
+      (value, repr)
+    }),
+    "[ \t!-~]{0,16}".prop_map(|value| {
+      let repr = quote(&value);
+      (value, repr)
+    }),
+  ]
+}
+
+/// strategy for generating the representation of a case-insensitive keyword in random case.
+///
+/// # Arguments
+/// * `keyword`: lower cased keyword.
+pub(crate) fn any_case(keyword: &'static str) -> impl Strategy<Value = String> {
+  prop_oneof![
+    3 => Just(keyword.to_string()),
+    1 => Just(keyword.to_ascii_uppercase()),
+    1 => crate::header::field_name_any_case(keyword.to_string()),
+  ]
+}
+
+/// strategy for generating list separator, a comma with optional whitespace.
+pub(crate) fn list_separator() -> impl Strategy<Value = String> {
+  prop_oneof![Just(", ".to_string()), Just(",".to_string()), "[ \t]{0,2},[ \t]{0,2}"]
+}
+
+/// strategy for generating comma separated list, elements are joined with [`list_separator`].
+///
+/// > `1#element => element *( OWS "," OWS element )`
+///
+/// # Arguments
+/// * `element`: strategy generating an element and its representation.
+/// * `count_range`: range of the number of elements.
+///
+/// # Returns
+/// elements and the list representation.
+pub(crate) fn list<T: Debug>(
+  element: impl Strategy<Value = (T, String)>,
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Vec<T>, String)> {
+  vec((element, list_separator()), count_range).prop_map(|elements| {
+    let mut repr = String::new();
+    let elements = elements
+      .into_iter()
+      .enumerate()
+      .map(|(i, ((element, element_repr), separator))| {
+        if i > 0 {
+          repr.push_str(&separator);
+        }
+        repr.push_str(&element_repr);
+        element
+      })
+      .collect();
+    (elements, repr)
+  })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::tests::is_tchar;
+
+  /// unquote a `quoted-string`, or return the token as is.
+  pub(crate) fn unquote(repr: &str) -> String {
+    let Some(inner) = repr.strip_prefix('"').and_then(|repr| repr.strip_suffix('"')) else {
+      return repr.to_string();
+    };
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+      value.push(if c == '\\' { chars.next().unwrap() } else { c });
+    }
+    value
+  }
+
+  /// split a list on commas outside of quoted strings, trimming whitespace.
+  pub(crate) fn split_list(repr: &str, separator: char) -> Vec<&str> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in repr.char_indices() {
+      match c {
+        _ if escaped => escaped = false,
+        '\\' if quoted => escaped = true,
+        '"' => quoted = !quoted,
+        _ if c == separator && !quoted => {
+          items.push(repr[start..i].trim_matches([' ', '\t']));
+          start = i + 1;
+        }
+        _ => {}
+      }
+    }
+    items.push(repr[start..].trim_matches([' ', '\t']));
+    items
+  }
+
+  proptest! {
+    #[test]
+    fn parameter_value_works((value, repr) in parameter_value()) {
+      assert_eq!(unquote(&repr), value);
+      if !repr.starts_with('"') {
+        assert!(repr.chars().all(is_tchar));
+      }
+    }
+  }
+}
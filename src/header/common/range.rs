@@ -0,0 +1,94 @@
+//! `Range` header field (RFC 9110 section 14.2) strategies.
+
+use proptest::{
+  option::of,
+  prelude::{Strategy, any},
+  prop_oneof,
+};
+
+use crate::header::common::{any_case, list};
+
+/// `Range` header field name.
+pub const RANGE: &str = "range";
+
+/// byte range specifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeSpec {
+  /// `first-pos "-" [ last-pos ]`, inclusive positions, `last >= first`.
+  Int { first: u64, last: Option<u64> },
+  /// `"-" suffix-length`, last octets of the representation.
+  Suffix(u64),
+}
+
+impl std::fmt::Display for RangeSpec {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Int { first, last: Some(last) } => write!(f, "{first}-{last}"),
+      Self::Int { first, last: None } => write!(f, "{first}-"),
+      Self::Suffix(length) => write!(f, "-{length}"),
+    }
+  }
+}
+
+/// strategy for generating byte range specifier.
+///
+/// > `int-range = first-pos "-" [ last-pos ]`
+/// > `suffix-range = "-" suffix-length`
+pub fn range_spec() -> impl Strategy<Value = RangeSpec> {
+  prop_oneof![
+    3 => (0..=u64::MAX / 2, of(0..=u64::MAX / 2))
+      .prop_map(|(first, length)| RangeSpec::Int { first, last: length.map(|length| first + length) }),
+    1 => (0..=10_000u64, of(0..=10_000u64))
+      .prop_map(|(first, length)| RangeSpec::Int { first, last: length.map(|length| first + length) }),
+    1 => any::<u64>().prop_map(RangeSpec::Suffix),
+  ]
+}
+
+/// strategy for generating `Range` header field value with the `bytes` unit.
+///
+/// > `ranges-specifier = range-unit "=" range-set`
+/// > `range-set = 1#range-spec`
+///
+/// # Returns
+/// range specifiers and their representation.
+pub fn range() -> impl Strategy<Value = (Vec<RangeSpec>, String)> {
+  let spec = range_spec().prop_map(|spec| {
+    let repr = spec.to_string();
+    (spec, repr)
+  });
+  (any_case("bytes"), list(spec, 1..=5))
+    .prop_map(|(unit, (specs, specs_repr))| (specs, format!("{unit}={specs_repr}")))
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::header::common::tests::split_list;
+
+  proptest! {
+    #[test]
+    fn range_works((specs, repr) in range()) {
+      let (unit, set) = repr.split_once('=').unwrap();
+      assert!(unit.eq_ignore_ascii_case("bytes"));
+
+      let parsed = split_list(set, ',')
+        .into_iter()
+        .map(|spec| match spec.split_once('-').unwrap() {
+          ("", length) => RangeSpec::Suffix(length.parse().unwrap()),
+          (first, "") => RangeSpec::Int { first: first.parse().unwrap(), last: None },
+          (first, last) => {
+            RangeSpec::Int { first: first.parse().unwrap(), last: Some(last.parse().unwrap()) }
+          }
+        })
+        .collect::<Vec<_>>();
+      assert_eq!(parsed, specs);
+      for spec in specs {
+        if let RangeSpec::Int { first, last: Some(last) } = spec {
+          assert!(first <= last);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,251 @@
+//! HTTP header field strategies.
+
+use std::ops::RangeInclusive;
+
+use proptest::{
+  collection::vec,
+  prelude::{Strategy, any},
+  sample::Index,
+};
+
+pub mod common;
+
+/// HTTP header field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderField {
+  /// field name, lower cased.
+  pub name: String,
+  /// field value, without leading and trailing whitespaces.
+  pub value: String,
+}
+
+/// strategy for generating header field name.
+///
+/// field name is a `token`, it is generated in lower case which is the canonical form and the
+/// only one allowed by HTTP/2 and HTTP/3.
+pub fn field_name() -> impl Strategy<Value = String> {
+  "[!#$%&'*+\\-.^_`|~0-9a-z]{1,24}"
+}
+
+/// strategy for generating header field value.
+///
+/// field value is made of visible ASCII characters, spaces and horizontal tabs, but it never
+/// starts or ends with a whitespace.
+///
+/// > `field-value = *field-content`
+/// > `field-content = field-vchar [ 1*( SP / HTAB / field-vchar ) field-vchar ]`
+pub fn field_value() -> impl Strategy<Value = String> {
+  "([!-~]([ \t!-~]{0,62}[!-~])?)?"
+}
+
+/// strategy for generating header field.
+///
+/// # Returns
+/// [`HeaderField`] and its representation: `<name>: <value>`.
+pub fn header_field() -> impl Strategy<Value = (HeaderField, String)> {
+  (field_name(), field_value()).prop_map(|(name, value)| {
+    let repr = format!("{name}: {value}");
+    (HeaderField { name, value }, repr)
+  })
+}
+
+/// strategy for generating list of header fields.
+///
+/// # Arguments
+/// * `count_range`: range of the number of header fields.
+///
+/// # Returns
+/// list of [`HeaderField`] and its representation, header fields are separated with `\r\n`.
+pub fn header_fields(
+  count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Vec<HeaderField>, String)> {
+  vec(header_field(), count_range).prop_map(|fields| {
+    let (fields, reprs): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+    (fields, reprs.join("\r\n"))
+  })
+}
+
+/// strategy for generating optional whitespace: spaces and horizontal tabs.
+///
+/// > `OWS = *( SP / HTAB )`
+pub fn ows() -> impl Strategy<Value = String> {
+  "[ \t]{0,3}"
+}
+
+/// strategy for generating the representation of a field name in random case.
+///
+/// # Arguments
+/// * `name`: lower cased field name.
+pub fn field_name_any_case(name: String) -> impl Strategy<Value = String> {
+  vec(any::<bool>(), name.len()).prop_map(move |upper| {
+    name
+      .chars()
+      .zip(upper)
+      .map(|(c, upper)| if upper { c.to_ascii_uppercase() } else { c })
+      .collect()
+  })
+}
+
+/// strategy for generating header field representation with random name case and optional
+/// whitespace around the value.
+///
+/// # Arguments
+/// * `field`: header field in canonical form.
+///
+/// # Returns
+/// the header field and its representation: `<Name>:<OWS><value><OWS>`.
+pub fn header_field_variant(field: HeaderField) -> impl Strategy<Value = (HeaderField, String)> {
+  (field_name_any_case(field.name.clone()), ows(), ows()).prop_map(
+    move |(name, leading, trailing)| {
+      let repr = format!("{name}:{leading}{}{trailing}", field.value);
+      (field.clone(), repr)
+    },
+  )
+}
+
+/// strategy for generating header field representation folded over several lines with
+/// `obs-fold`.
+///
+/// the value is folded before its first character or at its single spaces, so that replacing
+/// every `obs-fold` with a single space gives back the value. Obsolete line folding is invalid,
+/// a server must either reject the message or replace every `obs-fold` with one or more spaces
+/// (RFC 9112 section 5.2).
+///
+/// > `obs-fold = OWS CRLF RWS`
+///
+/// # Arguments
+/// * `field`: header field in canonical form.
+///
+/// # Returns
+/// the header field and its representation, with at least one `obs-fold`.
+pub fn header_field_obs_fold(field: HeaderField) -> impl Strategy<Value = (HeaderField, String)> {
+  let bytes = field.value.as_bytes();
+  let folds = std::iter::once(0)
+    .chain(field.value.match_indices(' ').map(|(i, _)| i).filter(|i| {
+      // whitespace around an obs-fold is part of it.
+      ![bytes[i - 1], bytes[i + 1]].iter().any(|b| *b == b' ' || *b == b'\t')
+    }))
+    .collect::<Vec<_>>();
+  let fold_count = folds.len();
+
+  (
+    field_name_any_case(field.name.clone()),
+    vec(any::<bool>(), fold_count),
+    any::<Index>(),
+    vec((ows(), "[ \t]{1,3}"), fold_count),
+  )
+    .prop_map(move |(name, folded, forced, whitespaces)| {
+      let forced = forced.index(fold_count);
+      let mut repr = format!("{name}:");
+      let mut start = 0;
+      for (i, ((position, folded), (ows, rws))) in
+        folds.iter().zip(folded).zip(whitespaces).enumerate()
+      {
+        if !folded && i != forced {
+          continue;
+        }
+        repr.push_str(&field.value[start..*position]);
+        repr.push_str(&format!("{ows}\r\n{rws}"));
+        // the folded space is replaced by the obs-fold.
+        start = if *position == 0 && i == 0 { 0 } else { position + 1 };
+      }
+      repr.push_str(&field.value[start..]);
+      (field.clone(), repr)
+    })
+}
+
+/// strategy for generating header field representation with whitespace between the field name
+/// and the colon.
+///
+/// such a header field must be rejected (RFC 9112 section 5.1).
+///
+/// # Arguments
+/// * `field`: header field in canonical form.
+///
+/// # Returns
+/// the header field and its representation: `<Name><RWS>:<OWS><value><OWS>`.
+pub fn header_field_whitespace_before_colon(
+  field: HeaderField,
+) -> impl Strategy<Value = (HeaderField, String)> {
+  (header_field_variant(field), "[ \t]{1,3}").prop_map(|((field, repr), whitespace)| {
+    let colon = field.name.len();
+    let repr = format!("{}{whitespace}{}", &repr[..colon], &repr[colon..]);
+    (field, repr)
+  })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  pub(crate) fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+  }
+
+  pub(crate) fn header_field_asserts(field: &HeaderField) {
+    assert!(
+      !field.name.is_empty() && field.name.chars().all(is_tchar),
+      "field name should be a token but got {:?}",
+      field.name
+    );
+    assert_eq!(field.name, field.name.to_ascii_lowercase(), "field name should be lower case");
+    assert_eq!(field.value.trim_matches([' ', '\t']), field.value, "field value should be trimmed");
+    assert!(
+      field.value.chars().all(|c| c == ' ' || c == '\t' || c.is_ascii_graphic()),
+      "unexpected character in field value {:?}",
+      field.value
+    );
+  }
+
+  proptest! {
+    #[test]
+    fn header_fields_works((fields, repr) in header_fields(0..=20)) {
+      let lines = if repr.is_empty() { vec![] } else { repr.split("\r\n").collect::<Vec<_>>() };
+      assert_eq!(fields.len(), lines.len(), "expected {} header fields in {repr:?}", fields.len());
+      for (field, line) in fields.iter().zip(lines) {
+        header_field_asserts(field);
+        let (name, value) = line.split_once(':').unwrap();
+        assert_eq!(name, field.name);
+        assert_eq!(value.trim_matches([' ', '\t']), field.value);
+      }
+    }
+
+    #[test]
+    fn header_field_variant_works(
+      (field, repr) in header_field().prop_flat_map(|(field, _)| header_field_variant(field))
+    ) {
+      header_field_asserts(&field);
+      let (name, value) = repr.split_once(':').unwrap();
+      assert_eq!(name.to_ascii_lowercase(), field.name);
+      assert_eq!(value.trim_matches([' ', '\t']), field.value);
+    }
+
+    #[test]
+    fn header_field_obs_fold_works(
+      (field, repr) in header_field().prop_flat_map(|(field, _)| header_field_obs_fold(field))
+    ) {
+      let (name, value) = repr.split_once(':').unwrap();
+      assert_eq!(name.to_ascii_lowercase(), field.name);
+      let lines = value.split("\r\n").collect::<Vec<_>>();
+      assert!(lines.len() > 1, "expected obs-fold in {repr:?}");
+      assert!(
+        lines[1..].iter().all(|line| line.starts_with([' ', '\t'])),
+        "continuation lines should start with whitespace in {repr:?}"
+      );
+      let unfolded = lines.iter().map(|line| line.trim_matches([' ', '\t'])).collect::<Vec<_>>();
+      assert_eq!(unfolded.join(" ").trim_start_matches(' '), field.value);
+    }
+
+    #[test]
+    fn header_field_whitespace_before_colon_works(
+      (field, repr) in header_field()
+        .prop_flat_map(|(field, _)| header_field_whitespace_before_colon(field))
+    ) {
+      let (name, _) = repr.split_once(':').unwrap();
+      assert!(name.ends_with([' ', '\t']), "expected whitespace before colon in {repr:?}");
+      assert_eq!(name.trim_end_matches([' ', '\t']).to_ascii_lowercase(), field.name);
+    }
+  }
+}
@@ -1 +1,7 @@
+pub mod body;
+pub mod h2;
+pub mod h3;
+pub mod header;
+pub mod reads;
+pub mod request;
 pub mod request_line;
@@ -0,0 +1,266 @@
+//! fragmented delivery strategies.
+//!
+//! parsers read messages in pieces, which may be split at any octet. The representation of any
+//! generated message can be split into reads, and replayed through [`std::io::Read`] or, with
+//! the `tokio` and `futures-io` features, `AsyncRead`.
+
+use std::{collections::VecDeque, fmt::Debug, io};
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+  sample::Index,
+};
+
+/// random split of the representation.
+#[derive(Debug, Clone)]
+enum SplitChoice {
+  /// split at the given position.
+  At(Index),
+  /// one-byte read at the given position.
+  OneByte(Index),
+  /// zero-length read at the given position.
+  Empty(Index),
+}
+
+fn split_choice() -> impl Strategy<Value = SplitChoice> {
+  prop_oneof![
+    3 => any::<Index>().prop_map(SplitChoice::At),
+    1 => any::<Index>().prop_map(SplitChoice::OneByte),
+    1 => any::<Index>().prop_map(SplitChoice::Empty),
+  ]
+}
+
+/// split octets into fragments.
+fn fragments(input: &[u8], splits: &[SplitChoice]) -> Vec<Vec<u8>> {
+  let mut positions = vec![0, input.len()];
+  for split in splits {
+    match split {
+      SplitChoice::At(index) => positions.push(index.index(input.len() + 1)),
+      SplitChoice::OneByte(index) if !input.is_empty() => {
+        let start = index.index(input.len());
+        positions.extend([start, start + 1]);
+      }
+      SplitChoice::OneByte(_) => {}
+      // the same position twice makes an empty fragment.
+      SplitChoice::Empty(index) => positions.extend([index.index(input.len() + 1); 2]),
+    }
+  }
+  positions.sort_unstable();
+
+  // a single position is kept for non empty splits.
+  let mut bounds = Vec::<usize>::with_capacity(positions.len());
+  let mut empty = splits.iter().filter(|split| matches!(split, SplitChoice::Empty(_))).count();
+  for position in positions {
+    if bounds.last() == Some(&position) {
+      if empty == 0 {
+        continue;
+      }
+      empty -= 1;
+    }
+    bounds.push(position);
+  }
+
+  bounds.windows(2).map(|window| input[window[0]..window[1]].to_vec()).collect()
+}
+
+/// strategy for splitting the representation of generated values into reads.
+///
+/// fragments are random sized, one-byte and zero-length reads included. Shrinking removes
+/// splits, down to a single read of the whole representation.
+///
+/// # Arguments
+/// * `strategy`: strategy generating a model and its representation, like
+///   [`request_line`](crate::request_line::request_line).
+/// * `max_splits`: maximum number of splits.
+///
+/// # Returns
+/// the model and the fragments of its representation, in order.
+pub fn split_into_reads<M, R>(
+  strategy: impl Strategy<Value = (M, R)>,
+  max_splits: usize,
+) -> impl Strategy<Value = (M, Vec<Vec<u8>>)>
+where
+  M: Debug + Clone,
+  R: AsRef<[u8]> + Debug + Clone,
+{
+  strategy.prop_flat_map(move |value| (Just(value), vec(split_choice(), 0..=max_splits))).prop_map(
+    |((model, repr), splits)| {
+      let fragments = fragments(repr.as_ref(), &splits);
+      (model, fragments)
+    },
+  )
+}
+
+/// replay fragments as reads.
+///
+/// a read never returns more than the current fragment. With [`io::Read`], zero-length
+/// fragments are replayed as [`io::ErrorKind::Interrupted`] errors, since a zero-length read
+/// means end of file. With `AsyncRead`, they are replayed as a pending read which immediately
+/// wakes the task.
+#[derive(Debug, Clone)]
+pub struct ReadReplay {
+  fragments: VecDeque<Vec<u8>>,
+  /// position in the current fragment.
+  position: usize,
+}
+
+impl ReadReplay {
+  pub fn new(fragments: impl IntoIterator<Item = Vec<u8>>) -> Self {
+    Self { fragments: fragments.into_iter().collect(), position: 0 }
+  }
+
+  /// copy the current fragment, `None` for zero-length fragments.
+  fn read_fragment(&mut self, buf: &mut [u8]) -> Option<usize> {
+    let Some(fragment) = self.fragments.front() else {
+      return Some(0);
+    };
+    if fragment.is_empty() {
+      self.fragments.pop_front();
+      return None;
+    }
+
+    let len = buf.len().min(fragment.len() - self.position);
+    buf[..len].copy_from_slice(&fragment[self.position..self.position + len]);
+    self.position += len;
+    if self.position == fragment.len() {
+      self.fragments.pop_front();
+      self.position = 0;
+    }
+    Some(len)
+  }
+}
+
+impl io::Read for ReadReplay {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    self.read_fragment(buf).ok_or_else(|| io::ErrorKind::Interrupted.into())
+  }
+}
+
+#[cfg(feature = "futures-io")]
+impl futures_io::AsyncRead for ReadReplay {
+  fn poll_read(
+    mut self: std::pin::Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &mut [u8],
+  ) -> std::task::Poll<io::Result<usize>> {
+    match self.read_fragment(buf) {
+      Some(len) => std::task::Poll::Ready(Ok(len)),
+      None => {
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+      }
+    }
+  }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for ReadReplay {
+  fn poll_read(
+    mut self: std::pin::Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &mut tokio::io::ReadBuf<'_>,
+  ) -> std::task::Poll<io::Result<()>> {
+    match self.read_fragment(buf.initialize_unfilled()) {
+      Some(len) => {
+        buf.advance(len);
+        std::task::Poll::Ready(Ok(()))
+      }
+      None => {
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Read;
+
+  use proptest::proptest;
+
+  use super::*;
+  use crate::request_line::request_line;
+
+  /// read everything, one `read` call at a time, returning the length of every read.
+  fn read_all(mut reader: impl Read) -> (Vec<u8>, Vec<usize>) {
+    let mut output = vec![];
+    let mut lens = vec![];
+    let mut buf = [0; 4096];
+    loop {
+      match reader.read(&mut buf) {
+        Ok(0) => return (output, lens),
+        Ok(len) => {
+          output.extend_from_slice(&buf[..len]);
+          lens.push(len);
+        }
+        Err(error) if error.kind() == io::ErrorKind::Interrupted => lens.push(0),
+        Err(error) => panic!("unexpected error {error}"),
+      }
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn split_into_reads_works(
+      ((request_line, repr), fragments) in split_into_reads(
+        request_line(20, 50.try_into().unwrap(), 0..=10)
+          .prop_map(|(request_line, repr)| ((request_line, repr.clone()), repr)),
+        16,
+      )
+    ) {
+      assert_eq!(fragments.concat(), repr.as_bytes());
+      assert!(repr.starts_with(request_line.verb.to_string().as_str()));
+
+      let (output, lens) = read_all(ReadReplay::new(fragments.clone()));
+      assert_eq!(output, repr.as_bytes());
+      assert_eq!(lens, fragments.iter().map(Vec::len).collect::<Vec<_>>());
+    }
+  }
+
+  #[cfg(feature = "futures-io")]
+  #[test]
+  fn futures_io_replay_works() {
+    use std::{
+      pin::Pin,
+      task::{Context, Poll, Waker},
+    };
+
+    let mut replay = ReadReplay::new([b"ab".to_vec(), vec![], b"c".to_vec()]);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut buf = [0; 8];
+    let mut poll = |replay: &mut ReadReplay| {
+      futures_io::AsyncRead::poll_read(Pin::new(replay), &mut cx, &mut buf).map(|len| len.unwrap())
+    };
+    assert_eq!(poll(&mut replay), Poll::Ready(2));
+    assert_eq!(poll(&mut replay), Poll::Pending);
+    assert_eq!(poll(&mut replay), Poll::Ready(1));
+    assert_eq!(poll(&mut replay), Poll::Ready(0));
+  }
+
+  #[cfg(feature = "tokio")]
+  #[test]
+  fn tokio_replay_works() {
+    use std::{
+      pin::Pin,
+      task::{Context, Poll, Waker},
+    };
+
+    use tokio::io::ReadBuf;
+
+    let mut replay = ReadReplay::new([b"ab".to_vec(), vec![], b"c".to_vec()]);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut poll = |replay: &mut ReadReplay| {
+      let mut storage = [0; 8];
+      let mut buf = ReadBuf::new(&mut storage);
+      tokio::io::AsyncRead::poll_read(Pin::new(replay), &mut cx, &mut buf)
+        .map(|result| result.map(|()| buf.filled().to_vec()).unwrap())
+    };
+    assert_eq!(poll(&mut replay), Poll::Ready(b"ab".to_vec()));
+    assert_eq!(poll(&mut replay), Poll::Pending);
+    assert_eq!(poll(&mut replay), Poll::Ready(b"c".to_vec()));
+    assert_eq!(poll(&mut replay), Poll::Ready(vec![]));
+  }
+}
@@ -0,0 +1,191 @@
+//! `Host` header field mismatch strategies.
+//!
+//! HTTP/1.1 requests must carry exactly one `Host` header field, and a server receiving an
+//! absolute form target must ignore it in favour of the target authority (RFC 9112 section
+//! 3.2). These requests break the rule, for testing virtual host routing and its rejections.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  prelude::{BoxedStrategy, Strategy, any},
+  prop_oneof,
+  sample::Index,
+};
+
+use crate::{
+  header::{
+    HeaderField,
+    common::host::{HOST, HostHeader, host_header},
+  },
+  request::{HttpRequest, encode_request, request_with_line},
+  request_line::{
+    HttpRequestLine, request_line_with_version,
+    target::{RequestTarget, absolute_form::absolute},
+    verb::method_without_tunnel,
+    version::HttpVersion,
+  },
+};
+
+/// how the `Host` header field is wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostMismatch {
+  /// no `Host` header field.
+  Missing,
+  /// two `Host` header fields, with the same value or not.
+  Duplicated { same: bool },
+  /// absolute form target with a `Host` header field naming another host.
+  Conflicting,
+}
+
+impl HostMismatch {
+  /// whether the request must be rejected with `400 Bad Request`.
+  pub fn is_rejected(self) -> bool {
+    !matches!(self, Self::Conflicting)
+  }
+}
+
+/// request with a wrong `Host` header field.
+#[derive(Debug, Clone)]
+pub struct HostMismatchRequest {
+  /// request, with its header fields as sent.
+  pub request: HttpRequest,
+  pub mismatch: HostMismatch,
+  /// host a server following RFC 9112 routes the request to, `None` when it must be rejected.
+  pub target_host: Option<HostHeader>,
+}
+
+/// strategy for generating request line with an absolute form target, which `CONNECT` does not
+/// allow.
+fn absolute_request_line(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (HttpRequestLine, String)> {
+  (method_without_tunnel(), absolute(max_label_count, max_segments, query_count_range)).prop_map(
+    |((verb, verb_repr), (absolute, target_repr))| {
+      let version = HttpVersion::Http11;
+      let repr = format!("{verb_repr} {target_repr} {version}");
+      (HttpRequestLine { verb, target: RequestTarget::Absolute(absolute), version }, repr)
+    },
+  )
+}
+
+/// strategy for generating HTTP/1.1 request message with a wrong `Host` header field.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `max_body_len`: maximum length of the body.
+///
+/// # Returns
+/// [`HostMismatchRequest`] and its representation.
+pub fn host_mismatch_request(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  max_body_len: usize,
+) -> impl Strategy<Value = (HostMismatchRequest, Vec<u8>)> {
+  // boxed, the request strategy is large and shared by the mismatches.
+  let request = move |request_line: BoxedStrategy<(HttpRequestLine, String)>| {
+    request_with_line(request_line, max_label_count, max_body_len).boxed()
+  };
+  let any_request = || {
+    request(
+      request_line_with_version(
+        max_label_count,
+        max_segments,
+        query_count_range.clone(),
+        HttpVersion::Http11,
+      )
+      .boxed(),
+    )
+  };
+
+  prop_oneof![
+    any_request().prop_map(|(mut request, line_repr)| {
+      request.headers.remove(0);
+      (request, line_repr, HostMismatch::Missing)
+    }),
+    (any_request(), any::<bool>(), host_header(max_label_count), any::<Index>())
+      .prop_filter("same host", |((request, _), same, (_, other), _)| {
+        *same || !request.headers[0].value.eq_ignore_ascii_case(other)
+      })
+      .prop_map(|((mut request, line_repr), same, (_, other), position)| {
+        let value = if same { request.headers[0].value.clone() } else { other };
+        // after the first `Host` header field.
+        let position = position.index(request.headers.len()) + 1;
+        request.headers.insert(position, HeaderField { name: HOST.to_string(), value });
+        (request, line_repr, HostMismatch::Duplicated { same })
+      }),
+    (
+      request(absolute_request_line(max_label_count, max_segments, query_count_range).boxed()),
+      host_header(max_label_count),
+    )
+      .prop_filter("same host", |((request, _), (other, _))| {
+        !request.headers[0].value.eq_ignore_ascii_case(other.host_repr())
+          && !request.headers[0]
+            .value
+            .to_ascii_lowercase()
+            .starts_with(&format!("{}:", other.host_repr().to_ascii_lowercase()))
+      })
+      .prop_map(|((mut request, line_repr), (_, other))| {
+        request.headers[0].value = other;
+        (request, line_repr, HostMismatch::Conflicting)
+      }),
+  ]
+  .prop_map(|(request, line_repr, mismatch)| {
+    let repr = encode_request(&line_repr, &request.headers, &request.body);
+    let target_host = if mismatch.is_rejected() {
+      None
+    } else {
+      HostHeader::from_target(&request.request_line.target)
+    };
+    (HostMismatchRequest { request, mismatch, target_host }, repr)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::{request::tests::split_head, request_line::target::tests::target_form_asserts};
+
+  proptest! {
+    #[test]
+    fn host_mismatch_request_works(
+      (mismatch_request, repr) in host_mismatch_request(20, 50.try_into().unwrap(), 0..=10, 64)
+    ) {
+      let (_, lines, _) = split_head(&repr);
+      let hosts = lines
+        .iter()
+        .filter_map(|line| line.strip_prefix(b"host: "))
+        .map(|value| str::from_utf8(value).unwrap())
+        .collect::<Vec<_>>();
+      let target = &mismatch_request.request.request_line.target;
+
+      match mismatch_request.mismatch {
+        HostMismatch::Missing => assert!(hosts.is_empty()),
+        HostMismatch::Duplicated { same } => {
+          assert_eq!(hosts.len(), 2);
+          assert_eq!(hosts[0].eq_ignore_ascii_case(hosts[1]), same);
+        }
+        HostMismatch::Conflicting => {
+          assert_eq!(hosts.len(), 1);
+          let RequestTarget::Absolute(absolute) = target else {
+            panic!("expected absolute form target but got {target:?}");
+          };
+          let authority = HostHeader::from(absolute.authority.clone());
+          assert!(!hosts[0].eq_ignore_ascii_case(&authority.to_string()));
+          assert_eq!(
+            mismatch_request.target_host.as_ref().map(ToString::to_string),
+            Some(authority.to_string())
+          );
+        }
+      }
+      assert_eq!(mismatch_request.target_host.is_none(), mismatch_request.mismatch.is_rejected());
+      target_form_asserts(&mismatch_request.request.request_line.verb, target);
+    }
+  }
+}
@@ -0,0 +1,314 @@
+//! HTTP/1.1 request message strategies.
+//!
+//! a request message is a request line, header fields starting with `Host` (RFC 9112 section
+//! 3.2) and an optional body framed by `Content-Length` (RFC 9112 section 6).
+
+use std::{fmt::Debug, num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  collection::vec,
+  prelude::{Strategy, any},
+};
+
+use crate::{
+  header::{
+    HeaderField,
+    common::host::{HOST, HostHeader, host_header},
+    header_fields,
+  },
+  request_line::{
+    HttpRequestLine, request_line_with_method, request_line_with_version, target::RequestTarget,
+    verb::method_with_body, version::HttpVersion,
+  },
+};
+
+pub mod host;
+pub mod pipeline;
+pub mod smuggling;
+
+/// header fields framing the message body.
+pub(crate) const FRAMING_HEADERS: [&str; 2] = ["content-length", "transfer-encoding"];
+
+/// HTTP/1.1 request message.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+  pub request_line: HttpRequestLine,
+  /// header fields, `Host` first and `Content-Length` last when the body is not empty.
+  pub headers: Vec<HeaderField>,
+  pub body: Vec<u8>,
+}
+
+/// strategy for generating header fields which do not frame the body, `Host` excluded.
+pub(crate) fn non_framing_headers() -> impl Strategy<Value = Vec<HeaderField>> {
+  header_fields(0..=8).prop_map(|(fields, _)| {
+    fields
+      .into_iter()
+      .filter(|field| field.name != HOST && !FRAMING_HEADERS.contains(&field.name.as_str()))
+      .collect()
+  })
+}
+
+/// `Host` header field for the request target.
+///
+/// # Arguments
+/// * `target`: request target, whose authority is used for absolute and authority forms.
+/// * `host`: host used for origin and asterisk forms.
+pub(crate) fn host_field(target: &RequestTarget, host: HostHeader) -> HeaderField {
+  let host = HostHeader::from_target(target).unwrap_or(host);
+  HeaderField { name: HOST.to_string(), value: host.to_string() }
+}
+
+/// encode header fields, each followed by `\r\n`.
+pub(crate) fn encode_headers(headers: &[HeaderField], output: &mut Vec<u8>) {
+  for field in headers {
+    output.extend_from_slice(field.name.as_bytes());
+    output.extend_from_slice(b": ");
+    output.extend_from_slice(field.value.as_bytes());
+    output.extend_from_slice(b"\r\n");
+  }
+}
+
+/// encode chunks with the chunked transfer coding, without trailer fields.
+///
+/// empty chunks are skipped as a zero size chunk ends the body.
+pub(crate) fn encode_chunked<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Vec<u8> {
+  let mut output = vec![];
+  for chunk in chunks.into_iter().filter(|chunk| !chunk.is_empty()) {
+    output.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+    output.extend_from_slice(chunk);
+    output.extend_from_slice(b"\r\n");
+  }
+  output.extend_from_slice(b"0\r\n\r\n");
+  output
+}
+
+/// encode a request message.
+///
+/// # Arguments
+/// * `line_repr`: representation of the request line.
+/// * `headers`: header fields.
+/// * `body`: message body.
+pub(crate) fn encode_request(line_repr: &str, headers: &[HeaderField], body: &[u8]) -> Vec<u8> {
+  let mut repr = format!("{line_repr}\r\n").into_bytes();
+  encode_headers(headers, &mut repr);
+  repr.extend_from_slice(b"\r\n");
+  repr.extend_from_slice(body);
+  repr
+}
+
+/// strategy for generating HTTP/1.1 request message with the given request line.
+///
+/// # Arguments
+/// * `request_line`: strategy generating the request line and its representation.
+/// * `max_label_count`: maximum label count to use for the `Host` header field of origin and
+///   asterisk form targets.
+/// * `max_body_len`: maximum length of the body.
+///
+/// # Returns
+/// [`HttpRequest`] and the representation of its request line.
+pub(crate) fn request_with_line(
+  request_line: impl Strategy<Value = (HttpRequestLine, String)>,
+  max_label_count: usize,
+  max_body_len: usize,
+) -> impl Strategy<Value = (HttpRequest, String)> {
+  (
+    request_line,
+    host_header(max_label_count),
+    non_framing_headers(),
+    vec(any::<u8>(), 0..=max_body_len),
+  )
+    .prop_map(|((request_line, line_repr), (host, _), fields, body)| {
+      let body = if request_line.verb.allows_body() { body } else { vec![] };
+      let mut headers = vec![host_field(&request_line.target, host)];
+      headers.extend(fields);
+      if !body.is_empty() {
+        headers
+          .push(HeaderField { name: "content-length".to_string(), value: body.len().to_string() });
+      }
+
+      (HttpRequest { request_line, headers, body }, line_repr)
+    })
+}
+
+/// strategy for generating HTTP/1.1 request message.
+///
+/// the `Host` header field carries the target authority for absolute and authority form
+/// targets, and a random host otherwise. Methods which allow a body may get one, framed with
+/// `Content-Length`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `max_body_len`: maximum length of the body.
+///
+/// # Returns
+/// [`HttpRequest`] and its representation.
+pub fn request(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  max_body_len: usize,
+) -> impl Strategy<Value = (HttpRequest, Vec<u8>)> {
+  request_with_line(
+    request_line_with_version(
+      max_label_count,
+      max_segments,
+      query_count_range,
+      HttpVersion::Http11,
+    ),
+    max_label_count,
+    max_body_len,
+  )
+  .prop_map(|(request, line_repr)| {
+    let repr = encode_request(&line_repr, &request.headers, &request.body);
+    (request, repr)
+  })
+}
+
+/// strategy for generating HTTP/1.1 request message with the given body.
+///
+/// the method allows a body, see [`method_with_body`], and the target form is allowed for the
+/// method. The header fields describing the body are followed by `Content-Length`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `body`: strategy generating a body model, the header fields describing the body and the
+///   body, like [`multipart_form_data`](crate::body::multipart::multipart_form_data).
+///
+/// # Returns
+/// [`HttpRequest`], the body model and the request representation.
+pub fn request_with_body<M: Debug + Clone>(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  body: impl Strategy<Value = (M, Vec<HeaderField>, Vec<u8>)>,
+) -> impl Strategy<Value = (HttpRequest, M, Vec<u8>)> {
+  (
+    request_line_with_method(
+      method_with_body(),
+      max_label_count,
+      max_segments,
+      query_count_range,
+      HttpVersion::Http11,
+    ),
+    host_header(max_label_count),
+    non_framing_headers(),
+    body,
+  )
+    .prop_map(|((request_line, line_repr), (host, _), fields, (model, body_headers, body))| {
+      let mut headers = vec![host_field(&request_line.target, host)];
+      headers.extend(
+        fields
+          .into_iter()
+          .filter(|field| !body_headers.iter().any(|body_header| body_header.name == field.name)),
+      );
+      headers.extend(body_headers);
+      headers
+        .push(HeaderField { name: "content-length".to_string(), value: body.len().to_string() });
+
+      let repr = encode_request(&line_repr, &headers, &body);
+      (HttpRequest { request_line, headers, body }, model, repr)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::{
+    body::{StructuredBody, multipart::multipart_form_data, structured_body},
+    header::tests::header_field_asserts,
+    request_line::target::tests::target_form_asserts,
+  };
+
+  /// split a request message into its request line, header lines and remaining octets.
+  pub(crate) fn split_head(input: &[u8]) -> (&str, Vec<&[u8]>, &[u8]) {
+    let end = input.windows(4).position(|window| window == b"\r\n\r\n").expect("end of head");
+    let mut lines =
+      input[..end + 2].split(|b| *b == b'\n').filter(|line| !line.is_empty()).map(|line| {
+        assert_eq!(line.last(), Some(&b'\r'), "line should end with CRLF");
+        &line[..line.len() - 1]
+      });
+    let request_line = str::from_utf8(lines.next().unwrap_or_default()).unwrap();
+    (request_line, lines.collect(), &input[end + 4..])
+  }
+
+  proptest! {
+    #[test]
+    fn request_works((request, repr) in request(20, 50.try_into().unwrap(), 0..=10, 64)) {
+      let (request_line, lines, body) = split_head(&repr);
+      assert!(request_line.ends_with(" HTTP/1.1"), "unexpected request line {request_line:?}");
+      assert_eq!(request.request_line.version, HttpVersion::Http11);
+      target_form_asserts(&request.request_line.verb, &request.request_line.target);
+      assert_eq!(lines.len(), request.headers.len());
+      for (line, field) in lines.iter().zip(&request.headers) {
+        header_field_asserts(field);
+        assert_eq!(*line, format!("{}: {}", field.name, field.value).as_bytes());
+      }
+
+      let content_length = request.headers.iter().find(|field| field.name == "content-length");
+      assert_eq!(content_length.map(|field| field.value.parse::<usize>().unwrap()).unwrap_or_default(), body.len());
+      assert!(!request.headers.iter().any(|field| field.name == "transfer-encoding"));
+      assert_eq!(request.headers.iter().filter(|field| field.name == HOST).count(), 1);
+      assert_eq!(request.headers[0].name, HOST);
+      if let Some(host) = HostHeader::from_target(&request.request_line.target) {
+        assert_eq!(request.headers[0].value, host.to_string());
+      }
+      assert_eq!(body, request.body);
+    }
+
+    #[test]
+    fn request_with_body_works(
+      (request, multipart, repr) in request_with_body(
+        20,
+        50.try_into().unwrap(),
+        0..=10,
+        multipart_form_data(3, 32),
+      )
+    ) {
+      let (_, lines, body) = split_head(&repr);
+      assert!(request.request_line.verb.allows_body());
+      target_form_asserts(&request.request_line.verb, &request.request_line.target);
+      assert_eq!(lines.len(), request.headers.len());
+      let field = |name: &str| {
+        let fields = request.headers.iter().filter(|field| field.name == name).collect::<Vec<_>>();
+        assert_eq!(fields.len(), 1, "expected a single {name:?} field");
+        fields[0].value.clone()
+      };
+      assert_eq!(field("content-length"), body.len().to_string());
+      assert_eq!(field("content-type"), multipart.content_type().value);
+      assert_eq!(body, request.body);
+    }
+
+    #[test]
+    fn request_with_structured_body_works(
+      (request, structured, repr) in request_with_body(
+        20,
+        50.try_into().unwrap(),
+        0..=10,
+        structured_body(64),
+      )
+    ) {
+      let (_, _, body) = split_head(&repr);
+      let field = |name: &str| {
+        request.headers.iter().find(|field| field.name == name).map(|field| field.value.as_str())
+      };
+      assert_eq!(field("content-length"), Some(body.len().to_string().as_str()));
+      let media_type = field("content-type").unwrap().split(';').next().unwrap();
+      let expected = match structured {
+        StructuredBody::Json(_) => "application/json",
+        StructuredBody::Form(_) => "application/x-www-form-urlencoded",
+        StructuredBody::Text(_) => "text/plain",
+        StructuredBody::Octets(octets) => {
+          assert_eq!(octets, body);
+          "application/octet-stream"
+        }
+      };
+      assert_eq!(media_type, expected);
+    }
+  }
+}
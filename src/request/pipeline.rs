@@ -0,0 +1,210 @@
+//! pipelined HTTP/1.1 request strategies.
+//!
+//! keep-alive clients may send several requests without waiting for the responses, the
+//! requests are then concatenated in a single byte stream.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  collection::vec,
+  prelude::{Strategy, any},
+  prop_oneof,
+  sample::Index,
+};
+
+use crate::{
+  request::{HttpRequest, encode_request, request_with_line},
+  request_line::{request_line_with_method, verb::method_without_tunnel, version::HttpVersion},
+};
+
+/// header fields which end the pipeline: `Connection` may close the connection and `Upgrade`
+/// may switch it to another protocol.
+const PIPELINE_BREAKING_HEADERS: [&str; 2] = ["connection", "upgrade"];
+
+/// pipelined requests.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+  pub requests: Vec<HttpRequest>,
+  /// offset of the first octet of every request in the byte stream.
+  pub offsets: Vec<usize>,
+  /// sorted offsets at which the byte stream is split into reads.
+  ///
+  /// splits are biased towards message boundaries, so that a read ends right before, at or
+  /// right after the end of a message.
+  pub read_splits: Vec<usize>,
+}
+
+impl Pipeline {
+  /// split the byte stream into reads at [`Pipeline::read_splits`].
+  ///
+  /// reads are never empty.
+  pub fn reads<'a>(&self, repr: &'a [u8]) -> Vec<&'a [u8]> {
+    let mut reads = vec![];
+    let mut start = 0;
+    for end in self.read_splits.iter().copied().chain([repr.len()]) {
+      if end > start {
+        reads.push(&repr[start..end]);
+        start = end;
+      }
+    }
+    reads
+  }
+}
+
+/// random choice of a read split.
+#[derive(Debug, Clone)]
+enum SplitChoice {
+  /// anywhere in the byte stream.
+  Anywhere(Index),
+  /// around the start of a message.
+  Boundary { message: Index, delta: isize },
+}
+
+fn split_choice() -> impl Strategy<Value = SplitChoice> {
+  prop_oneof![
+    any::<Index>().prop_map(SplitChoice::Anywhere),
+    (any::<Index>(), -1..=1isize)
+      .prop_map(|(message, delta)| SplitChoice::Boundary { message, delta }),
+  ]
+}
+
+/// strategy for generating pipelined HTTP/1.1 requests.
+///
+/// see [`request`](crate::request::request). Requests never use `CONNECT` nor carry
+/// `Connection` or `Upgrade` header fields, since the octets following such a request may not
+/// be another request.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `request_count_range`: range of the number of requests.
+/// * `max_body_len`: maximum length of a request body.
+///
+/// # Returns
+/// [`Pipeline`] and the byte stream.
+pub fn pipeline(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  request_count_range: RangeInclusive<usize>,
+  max_body_len: usize,
+) -> impl Strategy<Value = (Pipeline, Vec<u8>)> {
+  (
+    vec(
+      request_with_line(
+        request_line_with_method(
+          method_without_tunnel(),
+          max_label_count,
+          max_segments,
+          query_count_range,
+          HttpVersion::Http11,
+        ),
+        max_label_count,
+        max_body_len,
+      )
+      .prop_map(|(mut request, line_repr)| {
+        request.headers.retain(|field| !PIPELINE_BREAKING_HEADERS.contains(&field.name.as_str()));
+        let repr = encode_request(&line_repr, &request.headers, &request.body);
+        (request, repr)
+      }),
+      request_count_range,
+    ),
+    vec(split_choice(), 0..=16),
+  )
+    .prop_map(|(requests, splits)| {
+      let mut repr = vec![];
+      let mut offsets = vec![];
+      let (requests, reprs): (Vec<_>, Vec<_>) = requests.into_iter().unzip();
+      for request_repr in reprs {
+        offsets.push(repr.len());
+        repr.extend_from_slice(&request_repr);
+      }
+
+      let mut read_splits = splits
+        .into_iter()
+        .map(|split| match split {
+          SplitChoice::Anywhere(index) => index.index(repr.len() + 1),
+          SplitChoice::Boundary { message, delta } => {
+            let boundary = offsets.get(message.index(offsets.len().max(1))).copied().unwrap_or(0);
+            boundary.saturating_add_signed(delta).min(repr.len())
+          }
+        })
+        .collect::<Vec<_>>();
+      read_splits.sort_unstable();
+      read_splits.dedup();
+
+      (Pipeline { requests, offsets, read_splits }, repr)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::request_line::verb::Method;
+
+  /// incremental parser returning the offset of every message it completes.
+  #[derive(Default)]
+  struct Parser {
+    buffer: Vec<u8>,
+    consumed: usize,
+    messages: Vec<(usize, Vec<u8>)>,
+  }
+
+  impl Parser {
+    fn feed(&mut self, input: &[u8]) {
+      self.buffer.extend_from_slice(input);
+      while let Some(end) = self.buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+        let head = str::from_utf8(&self.buffer[..end]).unwrap();
+        let content_length = head
+          .split("\r\n")
+          .skip(1)
+          .filter_map(|line| line.split_once(": "))
+          .find(|(name, _)| *name == "content-length")
+          .map_or(0, |(_, value)| value.parse::<usize>().unwrap());
+        let len = end + 4 + content_length;
+        if self.buffer.len() < len {
+          return;
+        }
+        let body = self.buffer[end + 4..len].to_vec();
+        self.messages.push((self.consumed, body));
+        self.buffer.drain(..len);
+        self.consumed += len;
+      }
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn pipeline_works(
+      (pipeline, repr) in pipeline(20, 50.try_into().unwrap(), 0..=10, 1..=8, 64)
+    ) {
+      assert_eq!(pipeline.offsets.len(), pipeline.requests.len());
+      assert_eq!(pipeline.offsets[0], 0);
+      assert!(pipeline.read_splits.iter().all(|split| *split <= repr.len()));
+
+      let reads = pipeline.reads(&repr);
+      assert!(reads.iter().all(|read| !read.is_empty()));
+      assert_eq!(reads.concat(), repr);
+
+      let mut parser = Parser::default();
+      for read in reads {
+        parser.feed(read);
+      }
+      assert!(parser.buffer.is_empty(), "trailing octets {:?}", parser.buffer);
+      assert_eq!(parser.messages.len(), pipeline.requests.len());
+      for ((offset, body), (expected_offset, request)) in
+        parser.messages.iter().zip(pipeline.offsets.iter().zip(&pipeline.requests))
+      {
+        assert_eq!(offset, expected_offset);
+        assert_eq!(body, &request.body);
+        assert_ne!(request.request_line.verb, Method::Connect);
+        assert!(
+          !request.headers.iter().any(|field| PIPELINE_BREAKING_HEADERS.contains(&field.name.as_str()))
+        );
+      }
+    }
+  }
+}
@@ -0,0 +1,428 @@
+//! HTTP request smuggling strategies.
+//!
+//! requests with ambiguous framing: conflicting `Content-Length` and `Transfer-Encoding`,
+//! obfuscated `Transfer-Encoding` and invalid `Content-Length`. Every request is followed by
+//! octets that a server misreading the framing would take for the next request, and carries
+//! the outcome expected from a server following RFC 9112 section 6.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  collection::vec,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+};
+
+use crate::{
+  header::{HeaderField, common::host::host_header},
+  request::{encode_chunked, encode_headers, host_field, non_framing_headers},
+  request_line::{HttpRequestLine, request_line_with_version, version::HttpVersion},
+};
+
+/// obfuscation of the `Transfer-Encoding` header field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferEncodingObfuscation {
+  /// `Transfer-Encoding: chunked `, trailing whitespace is not part of the value.
+  TrailingSpace,
+  /// `Transfer-Encoding:\tchunked`, horizontal tab is a valid separator.
+  TabSeparator,
+  /// `Transfer-Encoding: CHUNKED`, transfer coding names are case-insensitive.
+  UpperCase,
+  /// `Transfer-Encoding:\x0bchunked`, vertical tab is not whitespace.
+  VerticalTabSeparator,
+  /// `Transfer-Encoding: xchunked`, unknown transfer coding.
+  UnknownCoding,
+  /// `Transfer-Encoding: chunked, identity`, chunked is not the final transfer coding.
+  ChunkedNotFinal,
+  /// `Transfer-Encoding: chunked` followed by `Transfer-Encoding: x`, combined as `chunked, x`.
+  DuplicateHeader,
+  /// `Transfer-Encoding : chunked`, whitespace between the field name and colon.
+  SpaceBeforeColon,
+}
+
+impl TransferEncodingObfuscation {
+  /// header lines, without `\r\n`.
+  fn lines(self) -> Vec<&'static [u8]> {
+    match self {
+      Self::TrailingSpace => vec![b"Transfer-Encoding: chunked "],
+      Self::TabSeparator => vec![b"Transfer-Encoding:\tchunked"],
+      Self::UpperCase => vec![b"Transfer-Encoding: CHUNKED"],
+      Self::VerticalTabSeparator => vec![b"Transfer-Encoding:\x0bchunked"],
+      Self::UnknownCoding => vec![b"Transfer-Encoding: xchunked"],
+      Self::ChunkedNotFinal => vec![b"Transfer-Encoding: chunked, identity"],
+      Self::DuplicateHeader => vec![b"Transfer-Encoding: chunked", b"Transfer-Encoding: x"],
+      Self::SpaceBeforeColon => vec![b"Transfer-Encoding : chunked"],
+    }
+  }
+
+  /// whether the field is a valid `Transfer-Encoding: chunked`.
+  pub fn is_chunked(self) -> bool {
+    matches!(self, Self::TrailingSpace | Self::TabSeparator | Self::UpperCase)
+  }
+}
+
+/// ambiguous framing of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Smuggling {
+  /// `Content-Length` covering the chunked body and the smuggled octets.
+  ClTe,
+  /// `Content-Length` covering the first chunk size line, the smuggled octets being in the
+  /// chunk.
+  TeCl,
+  /// obfuscated `Transfer-Encoding` with a `Content-Length` covering the chunked body and the
+  /// smuggled octets.
+  TeTe(TransferEncodingObfuscation),
+  /// two `Content-Length` values, either as two fields or as a list.
+  DuplicateContentLength { same: bool },
+  /// `Content-Length` with a `+` or `-` sign.
+  SignedContentLength,
+  /// `Content-Length` with leading zeros, which is valid.
+  LeadingZerosContentLength,
+}
+
+/// outcome expected from a server following RFC 9112.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+  /// the request must be rejected with `400 Bad Request` or `501 Not Implemented`, and the
+  /// connection closed.
+  Reject,
+  /// the request is accepted.
+  Accept {
+    /// length of the decoded body.
+    body_len: usize,
+    /// length of the request message, the following octets are not part of it.
+    message_len: usize,
+    /// whether the server must close the connection after responding, which is the case when
+    /// `Transfer-Encoding` overrides `Content-Length`.
+    close_connection: bool,
+  },
+}
+
+/// request with ambiguous framing.
+#[derive(Debug, Clone)]
+pub struct SmugglingRequest {
+  pub request_line: HttpRequestLine,
+  /// header fields not framing the body, `Host` first.
+  pub headers: Vec<HeaderField>,
+  pub smuggling: Smuggling,
+  /// body content, as decoded by a server following RFC 9112 when accepted.
+  pub body: Vec<u8>,
+  /// octets following the body, taken as another request by a server misreading the framing.
+  pub smuggled: Vec<u8>,
+  pub outcome: Outcome,
+}
+
+fn obfuscation() -> impl Strategy<Value = TransferEncodingObfuscation> {
+  proptest::sample::select(&[
+    TransferEncodingObfuscation::TrailingSpace,
+    TransferEncodingObfuscation::TabSeparator,
+    TransferEncodingObfuscation::UpperCase,
+    TransferEncodingObfuscation::VerticalTabSeparator,
+    TransferEncodingObfuscation::UnknownCoding,
+    TransferEncodingObfuscation::ChunkedNotFinal,
+    TransferEncodingObfuscation::DuplicateHeader,
+    TransferEncodingObfuscation::SpaceBeforeColon,
+  ])
+}
+
+fn smuggling_kind() -> impl Strategy<Value = Smuggling> {
+  prop_oneof![
+    Just(Smuggling::ClTe),
+    Just(Smuggling::TeCl),
+    obfuscation().prop_map(Smuggling::TeTe),
+    any::<bool>().prop_map(|same| Smuggling::DuplicateContentLength { same }),
+    Just(Smuggling::SignedContentLength),
+    Just(Smuggling::LeadingZerosContentLength),
+  ]
+}
+
+/// strategy for generating the smuggled octets: the start of another request.
+fn smuggled(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = Vec<u8>> {
+  request_line_with_version(max_label_count, max_segments, query_count_range, HttpVersion::Http11)
+    .prop_map(|(_, repr)| format!("{repr}\r\nx: ").into_bytes())
+}
+
+/// random decisions for building the framing header fields and body.
+#[derive(Debug, Clone)]
+struct FramingChoice {
+  te_first: bool,
+  list: bool,
+  other_length: usize,
+  negative: bool,
+  zeros: usize,
+}
+
+fn framing_choice() -> impl Strategy<Value = FramingChoice> {
+  (any::<bool>(), any::<bool>(), 0..=1024usize, any::<bool>(), 1..=4usize).prop_map(
+    |(te_first, list, other_length, negative, zeros)| FramingChoice {
+      te_first,
+      list,
+      other_length,
+      negative,
+      zeros,
+    },
+  )
+}
+
+/// framing header lines, payload following the head and expected outcome.
+fn framing(
+  smuggling: Smuggling,
+  body: &[u8],
+  smuggled: &[u8],
+  choice: &FramingChoice,
+) -> (Vec<Vec<u8>>, Vec<u8>, Outcome) {
+  let content_length = |value: &str| format!("Content-Length: {value}").into_bytes();
+  let with_te = |cl: Vec<u8>, te: Vec<Vec<u8>>| {
+    if choice.te_first {
+      te.into_iter().chain([cl]).collect()
+    } else {
+      [cl].into_iter().chain(te).collect()
+    }
+  };
+  let accept = |body_len, message_len, close_connection| Outcome::Accept {
+    body_len,
+    message_len,
+    close_connection,
+  };
+
+  match smuggling {
+    Smuggling::ClTe => {
+      let chunked = encode_chunked([body]);
+      let payload = [chunked.as_slice(), smuggled].concat();
+      (
+        with_te(
+          content_length(&payload.len().to_string()),
+          vec![b"Transfer-Encoding: chunked".to_vec()],
+        ),
+        payload,
+        accept(body.len(), chunked.len(), true),
+      )
+    }
+    Smuggling::TeCl => {
+      // the smuggled octets are the first chunk, the body follows.
+      let payload = encode_chunked([smuggled, body]);
+      let size_line = format!("{:x}\r\n", smuggled.len());
+      (
+        with_te(
+          content_length(&size_line.len().to_string()),
+          vec![b"Transfer-Encoding: chunked".to_vec()],
+        ),
+        payload.clone(),
+        accept(smuggled.len() + body.len(), payload.len(), true),
+      )
+    }
+    Smuggling::TeTe(obfuscation) => {
+      let chunked = encode_chunked([body]);
+      let payload = [chunked.as_slice(), smuggled].concat();
+      let te = obfuscation.lines().into_iter().map(<[u8]>::to_vec).collect();
+      let outcome = if obfuscation.is_chunked() {
+        accept(body.len(), chunked.len(), true)
+      } else {
+        Outcome::Reject
+      };
+      (with_te(content_length(&payload.len().to_string()), te), payload, outcome)
+    }
+    Smuggling::DuplicateContentLength { same } => {
+      let length = body.len().to_string();
+      let other = if same {
+        length.clone()
+      } else if choice.other_length == body.len() {
+        (body.len() + smuggled.len()).to_string()
+      } else {
+        choice.other_length.to_string()
+      };
+      let lines = if choice.list {
+        vec![content_length(&format!("{length}, {other}"))]
+      } else {
+        vec![content_length(&length), content_length(&other)]
+      };
+      let payload = [body, smuggled].concat();
+      let outcome = if same { accept(body.len(), body.len(), false) } else { Outcome::Reject };
+      (lines, payload, outcome)
+    }
+    Smuggling::SignedContentLength => {
+      let sign = if choice.negative { '-' } else { '+' };
+      (
+        vec![content_length(&format!("{sign}{}", body.len()))],
+        [body, smuggled].concat(),
+        Outcome::Reject,
+      )
+    }
+    Smuggling::LeadingZerosContentLength => (
+      vec![content_length(&format!("{}{}", "0".repeat(choice.zeros), body.len()))],
+      [body, smuggled].concat(),
+      accept(body.len(), body.len(), false),
+    ),
+  }
+}
+
+/// strategy for generating HTTP/1.1 request with ambiguous framing.
+///
+/// the request is followed by smuggled octets, the start of another request. When the request
+/// is accepted, the message length counts from the start of the representation.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `max_body_len`: maximum length of the body.
+///
+/// # Returns
+/// [`SmugglingRequest`] and its representation.
+pub fn smuggling_request(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  max_body_len: usize,
+) -> impl Strategy<Value = (SmugglingRequest, Vec<u8>)> {
+  (
+    request_line_with_version(
+      max_label_count,
+      max_segments,
+      query_count_range.clone(),
+      HttpVersion::Http11,
+    ),
+    (host_header(max_label_count), non_framing_headers()),
+    smuggling_kind(),
+    vec(any::<u8>(), 0..=max_body_len),
+    smuggled(max_label_count, max_segments, query_count_range),
+    framing_choice(),
+  )
+    .prop_map(
+      |((request_line, line_repr), ((host, _), fields), smuggling, body, smuggled, choice)| {
+        let mut headers = vec![host_field(&request_line.target, host)];
+        headers.extend(fields);
+        let (lines, payload, outcome) = framing(smuggling, &body, &smuggled, &choice);
+
+        let mut repr = format!("{line_repr}\r\n").into_bytes();
+        encode_headers(&headers, &mut repr);
+        for line in lines {
+          repr.extend_from_slice(&line);
+          repr.extend_from_slice(b"\r\n");
+        }
+        repr.extend_from_slice(b"\r\n");
+        let head_len = repr.len();
+        repr.extend_from_slice(&payload);
+
+        let outcome = match outcome {
+          Outcome::Accept { body_len, message_len, close_connection } => {
+            Outcome::Accept { body_len, message_len: head_len + message_len, close_connection }
+          }
+          Outcome::Reject => Outcome::Reject,
+        };
+        let body = if matches!(smuggling, Smuggling::TeCl) {
+          [smuggled.as_slice(), &body].concat()
+        } else {
+          body
+        };
+
+        (SmugglingRequest { request_line, headers, smuggling, body, smuggled, outcome }, repr)
+      },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+  use crate::request::tests::split_head;
+
+  /// decode a chunked body, returning the content and the length of the encoding.
+  fn decode_chunked(input: &[u8]) -> Option<(Vec<u8>, usize)> {
+    let mut content = vec![];
+    let mut position = 0;
+    loop {
+      let line_end = position + input[position..].windows(2).position(|w| w == b"\r\n")?;
+      let size =
+        usize::from_str_radix(str::from_utf8(&input[position..line_end]).ok()?, 16).ok()?;
+      position = line_end + 2;
+      if size == 0 {
+        // no trailer fields.
+        return (input.get(position..position + 2)? == b"\r\n").then_some((content, position + 2));
+      }
+      content.extend_from_slice(input.get(position..position + size)?);
+      position += size;
+      if input.get(position..position + 2)? != b"\r\n" {
+        return None;
+      }
+      position += 2;
+    }
+  }
+
+  /// strict RFC 9112 message framing, as a reference.
+  fn expected_outcome(repr: &[u8]) -> Outcome {
+    let (_, lines, payload) = split_head(repr);
+    let head_len = repr.len() - payload.len();
+    let mut transfer_encodings = vec![];
+    let mut content_lengths = vec![];
+
+    for line in lines {
+      let colon = line.iter().position(|b| *b == b':').unwrap();
+      let (name, value) = (&line[..colon], &line[colon + 1..]);
+      if name.ends_with(b" ") || name.ends_with(b"\t") {
+        return Outcome::Reject;
+      }
+      let start = value.iter().position(|b| *b != b' ' && *b != b'\t').unwrap_or(value.len());
+      let end = value.iter().rposition(|b| *b != b' ' && *b != b'\t').map_or(start, |end| end + 1);
+      let value = &value[start..end];
+      if value.iter().any(|b| !(b.is_ascii_graphic() || *b == b' ' || *b == b'\t')) {
+        return Outcome::Reject;
+      }
+      let value = str::from_utf8(value).unwrap();
+      let items = value.split(',').map(|item| item.trim_matches([' ', '\t']).to_ascii_lowercase());
+      match name.to_ascii_lowercase().as_slice() {
+        b"transfer-encoding" => transfer_encodings.extend(items),
+        b"content-length" => content_lengths.extend(items),
+        _ => {}
+      }
+    }
+
+    if !transfer_encodings.is_empty() {
+      if transfer_encodings != ["chunked"] {
+        return Outcome::Reject;
+      }
+      return match decode_chunked(payload) {
+        Some((content, len)) => Outcome::Accept {
+          body_len: content.len(),
+          message_len: head_len + len,
+          close_connection: !content_lengths.is_empty(),
+        },
+        None => Outcome::Reject,
+      };
+    }
+
+    if content_lengths
+      .iter()
+      .any(|value| value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()))
+    {
+      return Outcome::Reject;
+    }
+    let lengths =
+      content_lengths.iter().map(|value| value.parse::<usize>().unwrap()).collect::<Vec<_>>();
+    if lengths.windows(2).any(|pair| pair[0] != pair[1]) {
+      return Outcome::Reject;
+    }
+    let body_len = lengths.first().copied().unwrap_or_default();
+    Outcome::Accept { body_len, message_len: head_len + body_len, close_connection: false }
+  }
+
+  proptest! {
+    #[test]
+    fn smuggling_request_works(
+      (request, repr) in smuggling_request(20, 50.try_into().unwrap(), 0..=10, 64)
+    ) {
+      assert_eq!(expected_outcome(&repr), request.outcome, "unexpected outcome for {:?}", String::from_utf8_lossy(&repr));
+      if let Outcome::Accept { body_len, message_len, .. } = request.outcome {
+        assert_eq!(body_len, request.body.len());
+        assert!(repr.ends_with(&request.smuggled) || matches!(request.smuggling, Smuggling::TeCl));
+        assert!(message_len <= repr.len());
+      }
+      assert!(repr.windows(request.smuggled.len()).any(|window| window == request.smuggled));
+    }
+  }
+}
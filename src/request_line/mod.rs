@@ -2,18 +2,23 @@
 
 use std::{num::NonZero, ops::RangeInclusive};
 
-use proptest::prelude::Strategy;
+use proptest::prelude::{Just, Strategy};
 
-use crate::request_line::{target::RequestTarget, version::HttpVersion};
+use crate::request_line::{
+  target::{RequestTarget, RequestTargetBytes},
+  verb::Method,
+  version::HttpVersion,
+};
 
+pub mod pseudo_headers;
 pub mod target;
 pub mod verb;
 pub mod version;
 
 /// Http request line components.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HttpRequestLine {
-  pub verb: String,
+  pub verb: Method,
   pub target: RequestTarget,
   pub version: HttpVersion,
 }
@@ -36,29 +41,234 @@ pub fn request_line(
   query_count_range: RangeInclusive<usize>,
 ) -> impl Strategy<Value = (HttpRequestLine, String)> {
   (
-    verb::request_verb(),
+    verb::method(),
     target::target(max_label_count, max_segments, query_count_range),
     version::version(),
   )
-    .prop_map(|(verb, (target, target_repr), (version, version_repr))| {
-      let repr = format!("{verb} {target_repr} {version_repr}");
+    .prop_map(|((verb, verb_repr), (target, target_repr), (version, version_repr))| {
+      let repr = format!("{verb_repr} {target_repr} {version_repr}");
       (HttpRequestLine { verb, target, version }, repr)
     })
 }
 
+/// Http request line components as octets.
+#[derive(Debug, Clone)]
+pub struct HttpRequestLineBytes {
+  pub verb: Method,
+  pub target: RequestTargetBytes,
+  pub version: HttpVersion,
+}
+
+/// strategy for generating HTTP request line as octets.
+///
+/// Unlike [`request_line`], the target may contain raw `obs-text` octets and percent encoded
+/// sequences that are not valid UTF-8 once decoded.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form
+///   and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of absolute form
+///   and origin form.
+/// * `obs_text`: whether raw `obs-text` octets (`0x80` to `0xFF`) may appear in the target.
+///
+/// # Returns
+/// [`HttpRequestLineBytes`] and it representation.
+pub fn request_line_bytes(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  obs_text: bool,
+) -> impl Strategy<Value = (HttpRequestLineBytes, Vec<u8>)> {
+  (
+    verb::method(),
+    target::target_bytes(max_label_count, max_segments, query_count_range, obs_text),
+    version::version(),
+  )
+    .prop_map(|((verb, verb_repr), (target, target_repr), (version, version_repr))| {
+      let mut repr =
+        Vec::with_capacity(verb_repr.len() + target_repr.len() + version_repr.len() + 2);
+      repr.extend_from_slice(verb_repr.as_bytes());
+      repr.push(b' ');
+      repr.extend_from_slice(&target_repr);
+      repr.push(b' ');
+      repr.extend_from_slice(version_repr.as_bytes());
+      (HttpRequestLineBytes { verb, target, version }, repr)
+    })
+}
+
+/// strategy for generating HTTP request line with arbitrary `HTTP/x.y` version.
+///
+/// see [`version::version_arbitrary`], [`HttpVersion::is_known`] tells whether the version is
+/// supposed to be supported.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form,
+///   origin form and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of  absolute form
+///   and origin form.
+///
+/// # Returns
+/// [`HttpRequestLine`] and it representation.
+pub fn request_line_arbitrary_version(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (HttpRequestLine, String)> {
+  (
+    verb::method(),
+    target::target(max_label_count, max_segments, query_count_range),
+    version::version_arbitrary(),
+  )
+    .prop_map(|((verb, verb_repr), (target, target_repr), (version, version_repr))| {
+      let repr = format!("{verb_repr} {target_repr} {version_repr}");
+      (HttpRequestLine { verb, target, version }, repr)
+    })
+}
+
+/// strategy for generating HTTP request line with the given version.
+///
+/// the target form is allowed for the method, see [`target::target_for_method`].
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form,
+///   origin form and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of  absolute form
+///   and origin form.
+/// * `version`: version of the request line.
+///
+/// # Returns
+/// [`HttpRequestLine`] and it representation.
+///
+/// # Panics
+/// if the version is [`HttpVersion::Http09`], see [`simple_request_line`].
+pub fn request_line_with_version(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  version: HttpVersion,
+) -> impl Strategy<Value = (HttpRequestLine, String)> {
+  request_line_with_method(
+    verb::method(),
+    max_label_count,
+    max_segments,
+    query_count_range,
+    version,
+  )
+}
+
+/// strategy for generating HTTP request line with the given method and version.
+///
+/// the target form is allowed for the method, see [`target::target_for_method`].
+///
+/// # Arguments
+/// * `method`: strategy generating the method and its representation.
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form,
+///   origin form and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of  absolute form
+///   and origin form.
+/// * `version`: version of the request line.
+///
+/// # Returns
+/// [`HttpRequestLine`] and it representation.
+///
+/// # Panics
+/// if the version is [`HttpVersion::Http09`], see [`simple_request_line`].
+pub fn request_line_with_method(
+  method: impl Strategy<Value = (Method, String)>,
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  version: HttpVersion,
+) -> impl Strategy<Value = (HttpRequestLine, String)> {
+  assert_ne!(version, HttpVersion::Http09, "HTTP/0.9 request lines have no version");
+  method
+    .prop_flat_map(move |(verb, verb_repr)| {
+      let target =
+        target::target_for_method(&verb, max_label_count, max_segments, query_count_range.clone());
+      (Just((verb, verb_repr)), target)
+    })
+    .prop_map(move |((verb, verb_repr), (target, target_repr))| {
+      let repr = format!("{verb_repr} {target_repr} {version}");
+      (HttpRequestLine { verb, target, version: version.clone() }, repr)
+    })
+}
+
+/// strategy for generating HTTP/0.9 simple request line.
+///
+/// simple requests only support `GET` method with an origin form target and have no version:
+/// `GET <origin-form>`.
+///
+/// # Arguments
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`HttpRequestLine`] with [`HttpVersion::Http09`] version and it representation.
+pub fn simple_request_line(
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (HttpRequestLine, String)> {
+  target::origin_form::origin(max_segments, query_count_range).prop_map(|(origin, target_repr)| {
+    (
+      HttpRequestLine {
+        verb: Method::Get,
+        target: RequestTarget::Origin(origin),
+        version: HttpVersion::Http09,
+      },
+      format!("{} {target_repr}", Method::Get),
+    )
+  })
+}
+
 #[cfg(test)]
 mod tests {
-  use proptest::proptest;
+  use proptest::{proptest, strategy::ValueTree, test_runner::TestRunner};
 
   use super::*;
 
+  #[test]
+  fn request_line_bytes_generates_obs_text_and_invalid_utf8() {
+    let mut runner = TestRunner::deterministic();
+    let strategy = request_line_bytes(20, 50.try_into().unwrap(), 0..=20, true);
+    let (mut obs_text, mut invalid_utf8) = (false, false);
+    for _ in 0..256 {
+      let (request_line, repr) = strategy.new_tree(&mut runner).unwrap().current();
+      obs_text |= !repr.is_ascii();
+      let (path, query) = match request_line.target {
+        RequestTargetBytes::Origin(origin) => (Some(origin.path), origin.query),
+        RequestTargetBytes::Absolute(absolute) => (absolute.path, absolute.query),
+        RequestTargetBytes::Authority(_) | RequestTargetBytes::Asterisk => (None, None),
+      };
+      let decoded = path.into_iter().flat_map(|path| path.segments).chain(
+        query.into_iter().flatten().flat_map(|param| [param.key].into_iter().chain(param.value)),
+      );
+      invalid_utf8 |= decoded.into_iter().any(|octets| str::from_utf8(&octets).is_err());
+    }
+    assert!(obs_text, "expected raw obs-text octets in some request line");
+    assert!(invalid_utf8, "expected invalid UTF-8 once decoded in some request line");
+  }
+
+  #[test]
+  #[should_panic(expected = "HTTP/0.9 request lines have no version")]
+  fn request_line_with_version_rejects_http09() {
+    let _ = request_line_with_version(20, 50.try_into().unwrap(), 0..=20, HttpVersion::Http09);
+  }
+
   proptest! {
     #[test]
     fn request_line_works((request_line, repr) in request_line(20, 50.try_into().unwrap(), 0..=20)) {
       let mut request_line_components = repr.split_ascii_whitespace();
 
       let verb = request_line_components.next().unwrap();
-      assert_eq!(verb, request_line.verb, "expected to get verb {:?} but parsed {:?}", request_line.verb, verb);
+      assert_eq!(verb, request_line.verb.to_string(), "expected to get verb {:?} but parsed {:?}", request_line.verb, verb);
       verb::tests::request_verb_asserts(verb);
 
       let target = request_line_components.next().unwrap();
@@ -67,5 +277,45 @@ mod tests {
       let version = request_line_components.next().unwrap();
       version::tests::version_asserts(&request_line.version, version);
     }
+
+    #[test]
+    fn request_line_bytes_works((request_line, repr) in request_line_bytes(20, 50.try_into().unwrap(), 0..=20, true)) {
+      let request_line_components = repr.split(|b| *b == b' ').collect::<Vec<_>>();
+      assert_eq!(request_line_components.len(), 3, "expected 3 components in {repr:?}");
+
+      let verb = str::from_utf8(request_line_components[0]).unwrap();
+      assert_eq!(verb, request_line.verb.to_string(), "expected to get verb {:?} but parsed {:?}", request_line.verb, verb);
+
+      target::tests::target_bytes_asserts(&request_line.target, request_line_components[1]);
+
+      let version = str::from_utf8(request_line_components[2]).unwrap();
+      version::tests::version_asserts(&request_line.version, version);
+    }
+
+    #[test]
+    fn request_line_arbitrary_version_works((request_line, repr) in request_line_arbitrary_version(20, 50.try_into().unwrap(), 0..=20)) {
+      let version = repr.rsplit(' ').next().unwrap();
+      version::tests::version_asserts(&request_line.version, version);
+    }
+
+    #[test]
+    fn request_line_with_version_works(
+      (request_line, repr) in request_line_with_version(20, 50.try_into().unwrap(), 0..=20, HttpVersion::Http11)
+    ) {
+      assert!(repr.ends_with(" HTTP/1.1"), "unexpected request line {repr:?}");
+      target::tests::target_form_asserts(&request_line.verb, &request_line.target);
+      let target = repr.split(' ').nth(1).unwrap();
+      target::tests::target_asserts(&request_line.target, target);
+    }
+
+    #[test]
+    fn simple_request_line_works((request_line, repr) in simple_request_line(50.try_into().unwrap(), 0..=20)) {
+      assert_eq!(request_line.version, HttpVersion::Http09);
+      version::tests::version_asserts(&request_line.version, "");
+      let (verb, target) = repr.split_once(' ').unwrap();
+      assert_eq!(verb, "GET");
+      assert!(!target.contains(' '), "simple request should not have version but got {repr:?}");
+      target::tests::target_asserts(&request_line.target, target);
+    }
   }
 }
@@ -0,0 +1,261 @@
+//! HTTP/2 and HTTP/3 request pseudo header fields strategies.
+//!
+//! HTTP/2 and HTTP/3 have no textual request line, the request line components are conveyed as
+//! pseudo header fields instead (RFC 9113 section 8.3.1 and RFC 9114 section 4.3.1).
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  prelude::{Just, Strategy},
+  prop_oneof,
+  sample::select,
+};
+
+use crate::request_line::{
+  target::{
+    RequestTarget,
+    absolute_form::absolute,
+    authority_form::authority as authority_form,
+    components::{authority::authority, scheme::http_scheme},
+    origin_form::origin,
+  },
+  verb::{Method, method},
+  version::HttpVersion,
+};
+
+/// `:method` pseudo header field name.
+pub const METHOD: &str = ":method";
+/// `:scheme` pseudo header field name.
+pub const SCHEME: &str = ":scheme";
+/// `:authority` pseudo header field name.
+pub const AUTHORITY: &str = ":authority";
+/// `:path` pseudo header field name.
+pub const PATH: &str = ":path";
+/// `:protocol` pseudo header field name, only used by extended CONNECT (RFC 8441).
+pub const PROTOCOL: &str = ":protocol";
+
+/// protocols used with extended CONNECT.
+const PROTOCOLS: &[&str] = &["websocket", "webtransport", "connect-udp"];
+
+/// HTTP/2 or HTTP/3 request pseudo header fields.
+#[derive(Debug, Clone)]
+pub struct PseudoHeaders {
+  /// [`HttpVersion::Http2`] or [`HttpVersion::Http3`].
+  pub version: HttpVersion,
+  pub method: Method,
+  /// request target the pseudo header fields are derived from, as it would appear in the
+  /// HTTP/1.1 request line.
+  pub target: RequestTarget,
+  /// `:scheme` value, lower cased. `None` for CONNECT requests.
+  pub scheme: Option<String>,
+  /// `:authority` value, without user info.
+  pub authority: Option<String>,
+  /// `:path` value. `None` for CONNECT requests.
+  pub path: Option<String>,
+  /// `:protocol` value for extended CONNECT requests.
+  pub protocol: Option<String>,
+}
+
+impl PseudoHeaders {
+  /// pseudo header fields in the order they are sent.
+  pub fn fields(&self) -> Vec<(&'static str, String)> {
+    let mut fields = vec![(METHOD, self.method.to_string())];
+    if let Some(scheme) = self.scheme.as_ref() {
+      fields.push((SCHEME, scheme.clone()));
+    }
+    if let Some(authority) = self.authority.as_ref() {
+      fields.push((AUTHORITY, authority.clone()));
+    }
+    if let Some(path) = self.path.as_ref() {
+      fields.push((PATH, path.clone()));
+    }
+    if let Some(protocol) = self.protocol.as_ref() {
+      fields.push((PROTOCOL, protocol.clone()));
+    }
+    fields
+  }
+}
+
+/// strip user info from authority representation.
+fn strip_user_info(authority: &str) -> &str {
+  authority.rsplit_once('@').map_or(authority, |(_, host_port)| host_port)
+}
+
+/// split absolute form representation into `:authority` and `:path` values.
+///
+/// the fragment is dropped and an empty path becomes `/`.
+fn split_absolute(repr: &str) -> (String, String) {
+  let (_, rest) = repr.split_once("://").expect("absolute form has authority");
+  let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+  let path_start = rest.find(['/', '?']).unwrap_or(rest.len());
+  let (authority, path) = rest.split_at(path_start);
+
+  let path = if path.starts_with('/') { path.to_string() } else { format!("/{path}") };
+  (strip_user_info(authority).to_string(), path)
+}
+
+/// strategy for generating standard methods other than `CONNECT`.
+fn non_connect_method() -> impl Strategy<Value = Method> {
+  method()
+    .prop_map(|(method, _)| method)
+    .prop_filter("CONNECT method", |method| *method != Method::Connect)
+}
+
+/// strategy for generating request pseudo header fields.
+///
+/// the pseudo header fields are derived from a [`RequestTarget`]:
+/// * origin form: `:path` is the target, `:scheme` and `:authority` are generated as the
+///   HTTP/1.1 equivalent carries them in the `Host` header.
+/// * absolute form: `:scheme`, `:authority` and `:path` come from the target.
+/// * asterisk form: `OPTIONS` request with `*` as `:path`.
+/// * authority form: `CONNECT` request with only `:method` and `:authority`.
+/// * extended CONNECT: `CONNECT` request with `:protocol` and an origin form `:path`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`PseudoHeaders`] and the pseudo header fields in order.
+pub fn pseudo_headers(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (PseudoHeaders, Vec<(&'static str, String)>)> {
+  let origin_request = (
+    non_connect_method(),
+    origin(max_segments, query_count_range.clone()),
+    http_scheme(),
+    authority(max_label_count),
+  )
+    .prop_map(|(method, (origin, path), scheme, (_, authority))| PseudoHeaders {
+      version: HttpVersion::Http2,
+      method,
+      target: RequestTarget::Origin(origin),
+      scheme: Some(scheme.to_ascii_lowercase()),
+      authority: Some(strip_user_info(&authority).to_string()),
+      path: Some(path),
+      protocol: None,
+    });
+
+  let absolute_request =
+    (non_connect_method(), absolute(max_label_count, max_segments, query_count_range.clone()))
+      .prop_map(|(method, (absolute, repr))| {
+        let (authority, mut path) = split_absolute(&repr);
+        // `OPTIONS` request to the whole server uses `*` as path.
+        if method == Method::Options && path == "/" {
+          path = "*".to_string();
+        }
+        PseudoHeaders {
+          version: HttpVersion::Http2,
+          method,
+          scheme: Some(absolute.scheme.to_ascii_lowercase()),
+          target: RequestTarget::Absolute(absolute),
+          authority: Some(authority),
+          path: Some(path),
+          protocol: None,
+        }
+      });
+
+  let asterisk_request =
+    (http_scheme(), authority(max_label_count)).prop_map(|(scheme, (_, authority))| {
+      PseudoHeaders {
+        version: HttpVersion::Http2,
+        method: Method::Options,
+        target: RequestTarget::Asterisk,
+        scheme: Some(scheme.to_ascii_lowercase()),
+        authority: Some(strip_user_info(&authority).to_string()),
+        path: Some("*".to_string()),
+        protocol: None,
+      }
+    });
+
+  let connect_request =
+    authority_form(max_label_count).prop_map(|(authority, repr)| PseudoHeaders {
+      version: HttpVersion::Http2,
+      method: Method::Connect,
+      target: RequestTarget::Authority(authority),
+      scheme: None,
+      authority: Some(repr),
+      path: None,
+      protocol: None,
+    });
+
+  let extended_connect_request = (
+    origin(max_segments, query_count_range),
+    http_scheme(),
+    authority(max_label_count),
+    select(PROTOCOLS),
+  )
+    .prop_map(|((origin, path), scheme, (_, authority), protocol)| PseudoHeaders {
+      version: HttpVersion::Http2,
+      method: Method::Connect,
+      target: RequestTarget::Origin(origin),
+      scheme: Some(scheme.to_ascii_lowercase()),
+      authority: Some(strip_user_info(&authority).to_string()),
+      path: Some(path),
+      protocol: Some(protocol.to_string()),
+    });
+
+  (
+    prop_oneof![
+      4 => origin_request,
+      2 => absolute_request,
+      1 => asterisk_request,
+      1 => connect_request,
+      1 => extended_connect_request,
+    ],
+    prop_oneof![Just(HttpVersion::Http2), Just(HttpVersion::Http3)],
+  )
+    .prop_map(|(mut pseudo_headers, version)| {
+      pseudo_headers.version = version;
+      let fields = pseudo_headers.fields();
+      (pseudo_headers, fields)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  proptest! {
+    #[test]
+    fn pseudo_headers_works((pseudo_headers, fields) in pseudo_headers(20, 50.try_into().unwrap(), 0..=20)) {
+      assert!(matches!(pseudo_headers.version, HttpVersion::Http2 | HttpVersion::Http3));
+      assert_eq!(fields[0], (METHOD, pseudo_headers.method.to_string()));
+
+      let field = |name: &str| fields.iter().find(|(n, _)| *n == name).map(|(_, value)| value.as_str());
+      let authority = field(AUTHORITY);
+      assert!(authority.is_some(), "expected :authority in {fields:?}");
+      assert!(!authority.unwrap().contains('@'), "unexpected user info in {fields:?}");
+
+      match (&pseudo_headers.method, &pseudo_headers.target) {
+        (Method::Connect, RequestTarget::Authority(_)) => {
+          assert_eq!(fields.len(), 2, "CONNECT should only have :method and :authority but got {fields:?}");
+        }
+        (Method::Connect, _) => {
+          assert!(field(PROTOCOL).is_some(), "extended CONNECT should have :protocol but got {fields:?}");
+          assert!(field(SCHEME).is_some() && field(PATH).is_some());
+        }
+        (method, target) => {
+          assert!(field(PROTOCOL).is_none(), "unexpected :protocol in {fields:?}");
+          let scheme = field(SCHEME).unwrap();
+          assert!(matches!(scheme, "http" | "https"), "unexpected scheme {scheme:?}");
+          let path = field(PATH).unwrap();
+          if path == "*" {
+            assert_eq!(*method, Method::Options, "only OPTIONS can have * path but got {fields:?}");
+          } else {
+            assert!(path.starts_with('/'), "path should be absolute but got {path:?}");
+            assert!(!path.contains('#'), "path should not have fragment but got {path:?}");
+          }
+          if matches!(target, RequestTarget::Asterisk) {
+            assert_eq!(path, "*");
+          }
+        }
+      }
+    }
+  }
+}
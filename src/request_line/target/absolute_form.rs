@@ -0,0 +1,259 @@
+//! HTTP request target in absolute form strategies.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{option::of, prelude::Strategy};
+
+use crate::request_line::target::components::{
+  authority::{Authority, authority},
+  fragment::fragment,
+  path::{Path, PathBytes, path_absolute, path_absolute_bytes},
+  query::{QueryParam, QueryParamBytes, query, query_bytes},
+  scheme::http_scheme,
+};
+
+/// URL absolute form components
+#[derive(Debug, Clone)]
+pub struct AbsoluteForm {
+  pub scheme: String,
+  pub authority: Authority,
+  pub path: Option<Path>,
+  pub query: Option<Vec<QueryParam>>,
+  pub fragment: Option<String>,
+}
+
+/// strategy for generating target absolute form.
+pub fn absolute(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (AbsoluteForm, String)> {
+  (
+    http_scheme(),
+    authority(max_label_count),
+    of(path_absolute(max_segments)),
+    of(query(*query_count_range.start(), *query_count_range.end())),
+    of(fragment()),
+  )
+    .prop_map(|(scheme, (authority, authority_repr), path, query, fragment)| {
+      let repr = format!(
+        "{scheme}://{authority}{path}{query}{fragment}",
+        scheme = scheme,
+        authority = authority_repr,
+        path = if let Some(path) = path.as_ref() { path.1.as_str() } else { "" },
+        query = query.as_ref().map(|(_, query)| format!("?{query}")).as_deref().unwrap_or_default(),
+        fragment =
+          fragment.as_ref().map(|fragment| format!("#{fragment}")).as_deref().unwrap_or_default()
+      );
+
+      (
+        AbsoluteForm {
+          scheme,
+          authority,
+          path: path.map(|p| p.0),
+          query: query.map(|q| q.0),
+          fragment,
+        },
+        repr,
+      )
+    })
+}
+
+/// URL absolute form components as octets.
+///
+/// unlike [`AbsoluteForm`], there is no fragment as request targets never carry them.
+#[derive(Debug, Clone)]
+pub struct AbsoluteFormBytes {
+  pub scheme: String,
+  pub authority: Authority,
+  pub path: Option<PathBytes>,
+  pub query: Option<Vec<QueryParamBytes>>,
+}
+
+/// strategy for generating target absolute form as octets.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `obs_text`: whether raw `obs-text` octets (`0x80` to `0xFF`) may appear in the target.
+///
+/// # Returns
+/// [`AbsoluteFormBytes`] and its representation.
+pub fn absolute_bytes(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  obs_text: bool,
+) -> impl Strategy<Value = (AbsoluteFormBytes, Vec<u8>)> {
+  (
+    http_scheme(),
+    authority(max_label_count),
+    of(path_absolute_bytes(max_segments, obs_text)),
+    of(query_bytes(*query_count_range.start(), *query_count_range.end(), obs_text)),
+  )
+    .prop_map(|(scheme, (authority, authority_repr), path, query)| {
+      let mut repr = format!("{scheme}://{authority_repr}").into_bytes();
+      if let Some((_, path_repr)) = path.as_ref() {
+        repr.extend_from_slice(path_repr);
+      }
+      if let Some((_, query_repr)) = query.as_ref() {
+        repr.push(b'?');
+        repr.extend_from_slice(query_repr);
+      }
+
+      (
+        AbsoluteFormBytes { scheme, authority, path: path.map(|p| p.0), query: query.map(|q| q.0) },
+        repr,
+      )
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+  use claims::{assert_none, assert_ok};
+  use proptest::proptest;
+  use url::{Host, Url};
+
+  use super::*;
+  use crate::request_line::target::components::{
+    path::tests::path_bytes_asserts, query::tests::query_bytes_asserts,
+  };
+
+  pub(in super::super) fn absolute_asserts(absolute_form: &AbsoluteForm, repr: &str) {
+    let url = assert_ok!(Url::parse(repr), "should be good URL but got {repr}");
+    assert_eq!(absolute_form.scheme.to_ascii_lowercase(), url.scheme().to_ascii_lowercase());
+    if let Some(user_info) = absolute_form.authority.user_info.as_ref() {
+      assert_eq!(
+        user_info.username,
+        url.username(),
+        "expected to get username {:?} but got {:?}",
+        user_info.username,
+        url.username()
+      );
+      assert_eq!(
+        user_info.password.as_deref(),
+        url.password(),
+        "expected to get password {:?} but got {:?}",
+        user_info.password,
+        url.password()
+      );
+    } else {
+      assert!(url.username().is_empty(), "username should be empty if user info is absent");
+      assert_none!(url.password(), "password should not exist if user info is absent");
+    }
+
+    match (&absolute_form.authority.host, url.host()) {
+      (
+        crate::request_line::target::components::host::Host::Domain(domain),
+        Some(Host::Domain(domain2)),
+      ) => assert_eq!(
+        domain.to_lowercase(),
+        domain2.to_lowercase(),
+        "expected domain {:?} but parsed domain {:?}",
+        domain.to_lowercase(),
+        domain2.to_lowercase()
+      ),
+      (
+        crate::request_line::target::components::host::Host::Ipv6(ipv6_addr, _),
+        Some(Host::Ipv6(ipv6_addr2)),
+      ) => assert_eq!(
+        *ipv6_addr, ipv6_addr2,
+        "expected IP v6 {ipv6_addr:?} but parsed IP v6 {ipv6_addr:?}"
+      ),
+      (
+        crate::request_line::target::components::host::Host::Ipv4(ipv4_addr, _),
+        Some(Host::Ipv4(ipv4_addr2)),
+      ) => assert_eq!(
+        *ipv4_addr, ipv4_addr2,
+        "expected IP v6 {ipv4_addr:?} but parsed IP v6 {ipv4_addr:?}"
+      ),
+      _ => panic!("expected host {:?} but parsed {:?}", absolute_form.authority.host, url.host()),
+    }
+
+    match (absolute_form.authority.port, url.port()) {
+      (None | Some(80), None) => {}
+      (Some(port), Some(port2)) => {
+        assert_eq!(port, port2, "expected port but {port} parsed {port2}");
+      }
+      (port, port2) => panic!("expected port but {port:?} parsed {port2:?}"),
+    }
+
+    match (&absolute_form.path, url.path()) {
+      (Some(path), path2) => {
+        assert_eq!(path.normalized, path2, "expected path {path:?} but parsed {path2}");
+      }
+      (None, path) => {
+        assert!(path.is_empty() || path == "/", "expected path to be empty but parsed {path}");
+      }
+    }
+
+    match (absolute_form.query.as_deref(), url.query()) {
+      (None, None) => {}
+      (Some(query), Some(query2)) => {
+        let query = query
+          .iter()
+          .map(|query| format!("{}={}", query.key, query.value.as_deref().unwrap_or_default()))
+          .collect::<Vec<_>>()
+          .join("&");
+        assert_eq!(query, query2, "expected query {query:?} but parsed {query2:?}");
+      }
+      (query, query2) => panic!("expected query {query:?} but got {query2:?}"),
+    }
+
+    match (absolute_form.fragment.as_deref(), url.fragment()) {
+      (None, None) => {}
+      (Some(fragment), Some(fragment2)) => {
+        assert_eq!(fragment, fragment2, "expected fragment {fragment:?} but parsed {fragment2:?}");
+      }
+      (fragment, fragment2) => panic!("expected fragment {fragment:?} but parsed {fragment2:?}"),
+    }
+  }
+
+  pub(in super::super) fn absolute_bytes_asserts(absolute_form: &AbsoluteFormBytes, repr: &[u8]) {
+    let prefix = format!("{}://", absolute_form.scheme);
+    assert!(repr.starts_with(prefix.as_bytes()), "expected {prefix:?} prefix in {repr:?}");
+    let authority_end = repr[prefix.len()..]
+      .iter()
+      .position(|b| *b == b'/' || *b == b'?')
+      .map_or(repr.len(), |position| prefix.len() + position);
+    let query_start = repr[authority_end..]
+      .iter()
+      .position(|b| *b == b'?')
+      .map(|position| authority_end + position);
+
+    // authorities are ASCII, they are checked as an absolute form without path and query.
+    let authority_repr = assert_ok!(str::from_utf8(&repr[..authority_end]));
+    let authority_form = AbsoluteForm {
+      scheme: absolute_form.scheme.clone(),
+      authority: absolute_form.authority.clone(),
+      path: None,
+      query: None,
+      fragment: None,
+    };
+    absolute_asserts(&authority_form, authority_repr);
+
+    let path_repr = &repr[authority_end..query_start.unwrap_or(repr.len())];
+    match &absolute_form.path {
+      Some(path) => path_bytes_asserts(path, path_repr),
+      None => assert!(path_repr.is_empty(), "unexpected path in {repr:?}"),
+    }
+    match (&absolute_form.query, query_start) {
+      (Some(query), Some(query_start)) => query_bytes_asserts(query, &repr[query_start + 1..]),
+      (None, None) => {}
+      (query, _) => panic!("expected query {query:?} in {repr:?}"),
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn absolute_works((absolute_form, repr) in absolute(20, 50.try_into().unwrap(), 0..=20)) {
+      absolute_asserts(&absolute_form, &repr);
+    }
+
+    #[test]
+    fn absolute_bytes_works((absolute_form, repr) in absolute_bytes(20, 50.try_into().unwrap(), 0..=20, true)) {
+      absolute_bytes_asserts(&absolute_form, &repr);
+    }
+  }
+}
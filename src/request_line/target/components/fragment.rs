@@ -0,0 +1,70 @@
+//! URL fragment strategies.
+
+use std::{ops::RangeInclusive, sync::LazyLock};
+
+use array_concat::{concat_arrays, concat_arrays_size};
+use proptest::prelude::Strategy;
+
+use crate::request_line::target::components::{
+  PercentEncoding, SUB_DELIMS, UNRESERVED, UrlChar, canonical_percent_encoding,
+  char_diff_intervals, safe_and_percent_encoded_char,
+};
+
+static FRAGMENT_UNSAFE_CHARS: LazyLock<Vec<RangeInclusive<char>>> =
+  LazyLock::new(|| char_diff_intervals(&FRAGMENT_SAFE_CHARS));
+
+const FRAGMENT_SAFE_CHARS: [char; concat_arrays_size!(UNRESERVED, SUB_DELIMS) + 4] =
+  concat_arrays!(UNRESERVED, SUB_DELIMS, [':', '@', '/', '?']);
+
+fn chars(encoding: PercentEncoding) -> impl Strategy<Value = UrlChar> {
+  safe_and_percent_encoded_char(&FRAGMENT_SAFE_CHARS, &FRAGMENT_UNSAFE_CHARS, encoding)
+}
+
+fn fragment_repr(encoding: PercentEncoding) -> impl Strategy<Value = String> {
+  proptest::collection::vec(chars(encoding), 0..=125).prop_map(|chars| {
+    let mut fragment = String::new();
+    for c in chars {
+      match c {
+        UrlChar::Normal(c) => fragment.push(c),
+        UrlChar::PercentEncoded(s) => fragment.push_str(&s),
+      }
+    }
+
+    fragment
+  })
+}
+
+/// URL fragment.
+pub fn fragment() -> impl Strategy<Value = String> {
+  fragment_repr(PercentEncoding::default())
+}
+
+/// URL fragment, percent encoded following `encoding`.
+///
+/// # Returns
+/// fragment in canonical form (percent encoded unreserved characters are decoded and the
+/// remaining percent encodings are upper cased) and its representation.
+pub fn fragment_with_encoding(
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = (String, String)> {
+  fragment_repr(encoding).prop_map(|repr| (canonical_percent_encoding(&repr), repr))
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::{prelude::Just, proptest};
+
+  use super::*;
+  use crate::request_line::target::components::percent_encoding;
+
+  proptest! {
+    #[test]
+    fn fragment_with_encoding_works(
+      (encoding, (fragment, repr)) in percent_encoding().prop_flat_map(|encoding| {
+        (Just(encoding), fragment_with_encoding(encoding))
+      })
+    ) {
+      super::super::tests::percent_encoding_asserts(encoding, &repr, &fragment);
+    }
+  }
+}
@@ -0,0 +1,350 @@
+//! URL components strategies.
+
+pub mod authority;
+pub mod fragment;
+pub mod host;
+pub mod path;
+pub mod query;
+pub mod reference;
+pub mod scheme;
+pub mod uri;
+pub mod user_info;
+
+use std::{fmt::Write, ops::RangeInclusive};
+
+use array_concat::{concat_arrays, concat_arrays_size};
+use proptest::{
+  bool::weighted,
+  char::ranges,
+  prelude::{Just, Strategy, any},
+  prop_oneof,
+  sample::select,
+};
+
+const UNRESERVED: [char; 66] = [
+  'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+  't', 'u', 'v', 'w', 'x', 'y', 'z', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L',
+  'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4',
+  '5', '6', '7', '8', '9', '-', '.', '_', '~',
+];
+
+const SUB_DELIMS: [char; 11] = ['!', '$', '&', '\'', '(', ')', '*', '+', ',', ';', '='];
+const GEN_DELIMS: [char; 7] = [':', '/', '?', '#', '[', ']', '@'];
+#[expect(unused)]
+const RESERVED: [char; concat_arrays_size!(SUB_DELIMS, GEN_DELIMS)] =
+  concat_arrays!(SUB_DELIMS, GEN_DELIMS);
+
+/// URL character.
+#[derive(Debug, Clone)]
+enum UrlChar {
+  /// normal character.
+  Normal(char),
+  /// percent encoded character.
+  PercentEncoded(String),
+}
+
+/// case of the hexadecimal digits of percent encoded octets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexCase {
+  /// lower case digits, like `"%c3%a9"`.
+  #[default]
+  Lower,
+  /// upper case digits, like `"%C3%A9"`, which is the canonical form.
+  Upper,
+  /// randomly cased digits, like `"%c3%A9"` or `"%C3%a9"`.
+  Mixed,
+}
+
+/// percent encoding options.
+///
+/// RFC 3986 section 6.2.2 considers the following forms equivalent, URI normalizers are expected
+/// to turn them into a canonical form.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PercentEncoding {
+  /// case of percent encoded octets hexadecimal digits.
+  pub hex_case: HexCase,
+  /// whether unreserved characters are randomly percent encoded too, like `"%41"` for `'A'`.
+  pub over_encode: bool,
+}
+
+/// strategy for generating percent encoding options.
+pub fn percent_encoding() -> impl Strategy<Value = PercentEncoding> {
+  (prop_oneof![Just(HexCase::Lower), Just(HexCase::Upper), Just(HexCase::Mixed)], any::<bool>())
+    .prop_map(|(hex_case, over_encode)| PercentEncoding { hex_case, over_encode })
+}
+
+/// percent encodes `c`, `mask` bits select which hexadecimal digits are upper cased in case of
+/// [`HexCase::Mixed`].
+fn percent_encode_char(c: char, hex_case: HexCase, mask: u8) -> String {
+  let mut pct_encoded = String::with_capacity(c.len_utf8() * 3);
+  for (idx, byte) in c.encode_utf8(&mut [0u8; 4]).bytes().enumerate() {
+    let _ = match hex_case {
+      HexCase::Lower => write!(pct_encoded, "%{byte:02x}"),
+      HexCase::Upper => write!(pct_encoded, "%{byte:02X}"),
+      HexCase::Mixed => {
+        pct_encoded.push('%');
+        for (digit_idx, digit) in format!("{byte:02x}").chars().enumerate() {
+          if mask >> (idx * 2 + digit_idx) & 1 == 1 {
+            pct_encoded.push(digit.to_ascii_uppercase());
+          } else {
+            pct_encoded.push(digit);
+          }
+        }
+        Ok(())
+      }
+    };
+  }
+
+  pct_encoded
+}
+
+fn percent_encoded_char(
+  chars: impl Strategy<Value = char>,
+  hex_case: HexCase,
+) -> impl Strategy<Value = String> {
+  (chars, any::<u8>()).prop_map(move |(c, mask)| percent_encode_char(c, hex_case, mask))
+}
+
+/// turns percent encodings into their canonical form (RFC 3986 sections 6.2.2.1 and 6.2.2.2):
+/// percent encoded unreserved characters are decoded and the remaining percent encodings are
+/// upper cased.
+fn canonical_percent_encoding(input: &str) -> String {
+  let decoded = decode_unreserved(input);
+  let mut result = String::with_capacity(decoded.len());
+  let mut rest = decoded.as_str();
+  while let Some(pct_idx) = rest.find('%') {
+    result.push_str(&rest[..pct_idx]);
+    match rest.get(pct_idx + 1..pct_idx + 3) {
+      Some(hex) if hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+        result.push('%');
+        result.push_str(&hex.to_ascii_uppercase());
+        rest = &rest[pct_idx + 3..];
+      }
+      _ => {
+        result.push('%');
+        rest = &rest[pct_idx + 1..];
+      }
+    }
+  }
+  result.push_str(rest);
+
+  result
+}
+
+/// decodes percent encoded unreserved characters, as they are equivalent to their decoded
+/// form (RFC 3986 section 6.2.2.2).
+fn decode_unreserved(input: &str) -> String {
+  let mut result = String::with_capacity(input.len());
+  let mut rest = input;
+  while let Some(pct_idx) = rest.find('%') {
+    result.push_str(&rest[..pct_idx]);
+    rest = &rest[pct_idx..];
+    match rest.get(1..3).and_then(|hex| u8::from_str_radix(hex, 16).ok()).map(char::from) {
+      Some(c) if UNRESERVED.contains(&c) => {
+        result.push(c);
+        rest = &rest[3..];
+      }
+      _ => {
+        result.push('%');
+        rest = &rest[1..];
+      }
+    }
+  }
+  result.push_str(rest);
+
+  result
+}
+
+/// decodes every percent encoded octet of `input`, invalid percent encodings are kept as is.
+fn percent_decode(input: &[u8]) -> Vec<u8> {
+  let mut result = Vec::with_capacity(input.len());
+  let mut idx = 0;
+  while idx < input.len() {
+    let decoded = (input[idx] == b'%')
+      .then(|| input.get(idx + 1..idx + 3))
+      .flatten()
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    if let Some(byte) = decoded {
+      result.push(byte);
+      idx += 3;
+    } else {
+      result.push(input[idx]);
+      idx += 1;
+    }
+  }
+
+  result
+}
+
+fn char_diff_intervals(chars: &[char]) -> Vec<RangeInclusive<char>> {
+  let mut chars = chars.to_vec();
+  chars.sort_unstable();
+  chars.dedup();
+
+  if chars.is_empty() {
+    return vec!['\0'..=char::MAX];
+  }
+
+  let mut unsafe_chars = Vec::new();
+  if let Some(&first_safe) = chars.first()
+    && first_safe > '\0'
+  {
+    unsafe_chars.push('\0'..=char::from_u32(first_safe as u32 - 1).unwrap());
+  }
+
+  for window in chars.windows(2) {
+    let current = window[0] as u32;
+    let next = window[1] as u32;
+
+    if next - current > 1 {
+      unsafe_chars.push(char::from_u32(current + 1).unwrap()..=char::from_u32(next - 1).unwrap());
+    }
+  }
+
+  if let Some(&last) = chars.last()
+    && last < char::MAX
+  {
+    unsafe_chars.push(char::from_u32(last as u32 + 1).unwrap()..=char::MAX);
+  }
+
+  unsafe_chars
+}
+
+fn safe_and_percent_encoded_char(
+  safe_chars: &'static [char],
+  unsafe_chars_ranges: &[RangeInclusive<char>],
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = UrlChar> {
+  let over_encode_probability = if encoding.over_encode { 0.25 } else { 0.0 };
+  let safe_chars_strategy = (select(safe_chars), weighted(over_encode_probability), any::<u8>())
+    .prop_map(move |(c, over_encode, mask)| {
+      if over_encode && UNRESERVED.contains(&c) {
+        UrlChar::PercentEncoded(percent_encode_char(c, encoding.hex_case, mask))
+      } else {
+        UrlChar::Normal(c)
+      }
+    });
+
+  let unsafe_chars_strategy = percent_encoded_char(
+    ranges(std::borrow::Cow::Borrowed(unsafe_chars_ranges)),
+    encoding.hex_case,
+  )
+  .prop_map(UrlChar::PercentEncoded);
+
+  prop_oneof![98 => safe_chars_strategy, 2 => unsafe_chars_strategy]
+}
+
+fn url_chars_to_string(chars: Vec<UrlChar>) -> String {
+  let mut result = String::new();
+  for c in chars {
+    match c {
+      UrlChar::Normal(c) => result.push(c),
+      UrlChar::PercentEncoded(pct_encoded) => result.push_str(&pct_encoded),
+    }
+  }
+
+  result
+}
+
+/// URL octet.
+#[derive(Debug, Clone)]
+enum UrlOctet {
+  /// normal ASCII character.
+  Normal(u8),
+  /// percent encoded octet, which may not be valid UTF-8 once decoded.
+  PercentEncoded(u8),
+  /// raw `obs-text` octet (`0x80` to `0xFF`), forbidden by RFC 3986 but found in real traffic.
+  ObsText(u8),
+}
+
+/// strategy for generating URL octets.
+///
+/// `safe_chars` must only contain ASCII characters, every other octet is percent encoded, or
+/// sent as is if it is an `obs-text` octet and `obs_text` is `true`.
+fn safe_and_percent_encoded_octet(
+  safe_chars: &'static [char],
+  obs_text: bool,
+) -> impl Strategy<Value = UrlOctet> {
+  prop_oneof![
+    90 => select(safe_chars).prop_map(|c| UrlOctet::Normal(c as u8)),
+    7 => any::<u8>().prop_map(UrlOctet::PercentEncoded),
+    3 => (0x80..=0xffu8).prop_map(move |octet| {
+      if obs_text { UrlOctet::ObsText(octet) } else { UrlOctet::PercentEncoded(octet) }
+    }),
+  ]
+}
+
+/// # Returns
+/// decoded octets and their representation.
+fn url_octets_to_bytes(octets: Vec<UrlOctet>) -> (Vec<u8>, Vec<u8>) {
+  let mut decoded = Vec::with_capacity(octets.len());
+  let mut repr = Vec::with_capacity(octets.len());
+  for octet in octets {
+    match octet {
+      UrlOctet::Normal(octet) | UrlOctet::ObsText(octet) => {
+        decoded.push(octet);
+        repr.push(octet);
+      }
+      UrlOctet::PercentEncoded(octet) => {
+        decoded.push(octet);
+        repr.extend_from_slice(format!("%{octet:02x}").as_bytes());
+      }
+    }
+  }
+
+  (decoded, repr)
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::proptest;
+
+  use super::*;
+
+  fn percent_encodings(repr: &str) -> Vec<&str> {
+    repr.match_indices('%').filter_map(|(idx, _)| repr.get(idx + 1..idx + 3)).collect()
+  }
+
+  pub(super) fn percent_encoding_asserts(encoding: PercentEncoding, repr: &str, canonical: &str) {
+    for hex in percent_encodings(repr) {
+      match encoding.hex_case {
+        HexCase::Lower => {
+          assert!(!hex.contains(char::is_uppercase), "expected lower case hex but got {repr:?}");
+        }
+        HexCase::Upper => {
+          assert!(!hex.contains(char::is_lowercase), "expected upper case hex but got {repr:?}");
+        }
+        HexCase::Mixed => {}
+      }
+      if !encoding.over_encode {
+        let c = char::from(u8::from_str_radix(hex, 16).unwrap());
+        assert!(
+          !UNRESERVED.contains(&c),
+          "unreserved characters should not be encoded in {repr:?}"
+        );
+      }
+    }
+
+    for hex in percent_encodings(canonical) {
+      assert!(!hex.contains(char::is_lowercase), "expected canonical form but got {canonical:?}");
+      let c = char::from(u8::from_str_radix(hex, 16).unwrap());
+      assert!(!UNRESERVED.contains(&c), "expected canonical form but got {canonical:?}");
+    }
+    assert_eq!(canonical_percent_encoding(canonical), canonical, "canonical form should be stable");
+  }
+
+  proptest! {
+    #[test]
+    fn percent_encode_char_works(c in proptest::char::any(), encoding in percent_encoding(), mask in any::<u8>()) {
+      let pct_encoded = percent_encode_char(c, encoding.hex_case, mask);
+      assert_eq!(percent_decode(pct_encoded.as_bytes()), c.to_string().into_bytes());
+      percent_encoding_asserts(
+        PercentEncoding { over_encode: true, ..encoding },
+        &pct_encoded,
+        &canonical_percent_encoding(&pct_encoded),
+      );
+    }
+  }
+}
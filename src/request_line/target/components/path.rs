@@ -0,0 +1,509 @@
+//! URL path strategies.
+
+use std::{num::NonZero, ops::RangeInclusive, sync::LazyLock};
+
+use array_concat::{concat_arrays, concat_arrays_size};
+use proptest::{
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+use crate::request_line::target::components::{
+  PercentEncoding, SUB_DELIMS, UNRESERVED, UrlChar, canonical_percent_encoding,
+  char_diff_intervals, decode_unreserved, percent_decode, safe_and_percent_encoded_char,
+  safe_and_percent_encoded_octet, url_chars_to_string, url_octets_to_bytes,
+};
+
+static PATH_UNSAFE_CHARS: LazyLock<Vec<RangeInclusive<char>>> =
+  LazyLock::new(|| char_diff_intervals(&PATH_SAFE_CHARS));
+
+const PATH_SAFE_CHARS: [char; concat_arrays_size!(UNRESERVED, SUB_DELIMS) + 2] =
+  concat_arrays!(UNRESERVED, SUB_DELIMS, [':', '@']);
+
+fn pchar(encoding: PercentEncoding) -> impl Strategy<Value = UrlChar> {
+  safe_and_percent_encoded_char(&PATH_SAFE_CHARS, &PATH_UNSAFE_CHARS, encoding)
+}
+
+fn segment(
+  min_chars: usize,
+  max_chars: usize,
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = String> {
+  proptest::collection::vec(pchar(encoding), min_chars..max_chars).prop_map(url_chars_to_string)
+}
+
+fn segment_nz(max_chars: usize, encoding: PercentEncoding) -> impl Strategy<Value = String> {
+  segment(1, max_chars, encoding)
+}
+
+/// URL path.
+#[derive(Debug, Clone)]
+pub struct Path {
+  /// normalized path.
+  /// # Examples
+  /// * the path `"/foo/./bar"` will be normalized to `"/foo/bar"`
+  /// * the path `"/foo/../bar"` will be normalized to `"/bar"`
+  pub normalized: String,
+}
+
+/// removes `"."` and `".."` segments from `path` following RFC 3986 section 5.2.4.
+pub(crate) fn remove_dot_segments(path: &str) -> String {
+  fn pop_last_segment(output: &mut String) {
+    let last_slash = output.rfind('/').unwrap_or_default();
+    output.truncate(last_slash);
+  }
+
+  let mut input = path;
+  let mut output = String::with_capacity(path.len());
+  while !input.is_empty() {
+    if let Some(rest) = input.strip_prefix("../").or_else(|| input.strip_prefix("./")) {
+      input = rest;
+    } else if input.starts_with("/./") {
+      input = &input[2..];
+    } else if input == "/." {
+      input = "/";
+    } else if input.starts_with("/../") {
+      input = &input[3..];
+      pop_last_segment(&mut output);
+    } else if input == "/.." {
+      input = "/";
+      pop_last_segment(&mut output);
+    } else if input == "." || input == ".." {
+      input = "";
+    } else {
+      let segment_start = usize::from(input.starts_with('/'));
+      let segment_end = input[segment_start..].find('/').map_or(input.len(), |i| i + segment_start);
+      output.push_str(&input[..segment_end]);
+      input = &input[segment_end..];
+    }
+  }
+
+  output
+}
+
+fn path_rootless_repr(
+  max_segments: NonZero<usize>,
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = String> {
+  (
+    segment_nz(50, encoding),
+    proptest::collection::vec(segment(0, 50, encoding), 0..=max_segments.get()),
+  )
+    .prop_map(|(segment_nz, segments)| {
+      if segments.is_empty() {
+        segment_nz
+      } else {
+        format!("{segment_nz}/{segments}", segments = segments.join("/"))
+      }
+    })
+}
+
+/// rootless path with no query params and no fragment.
+/// # Returns
+/// [`Path`] and its raw representation.
+pub fn path_rootless(max_segments: NonZero<usize>) -> impl Strategy<Value = (Path, String)> {
+  path_rootless_repr(max_segments, PercentEncoding::default())
+    .prop_map(|repr| (Path { normalized: remove_dot_segments(&repr) }, repr))
+}
+
+/// absolute path with no query params and no fragment.
+/// #Returns
+/// [`Path`] and its raw representation.
+pub fn path_absolute(max_segments: NonZero<usize>) -> impl Strategy<Value = (Path, String)> {
+  path_rootless_repr(max_segments, PercentEncoding::default()).prop_map(|repr| {
+    let repr = format!("/{repr}");
+    (Path { normalized: remove_dot_segments(&repr) }, repr)
+  })
+}
+
+/// absolute path with no query params and no fragment, percent encoded following `encoding`.
+///
+/// the normalized path is in canonical form: percent encoded unreserved characters are decoded,
+/// the remaining percent encodings are upper cased and dot segments are removed.
+///
+/// # Returns
+/// [`Path`] and its raw representation.
+pub fn path_absolute_with_encoding(
+  max_segments: NonZero<usize>,
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = (Path, String)> {
+  path_rootless_repr(max_segments, encoding).prop_map(|repr| {
+    let repr = format!("/{repr}");
+    (Path { normalized: remove_dot_segments(&canonical_percent_encoding(&repr)) }, repr)
+  })
+}
+
+/// URL path octets.
+#[derive(Debug, Clone)]
+pub struct PathBytes {
+  /// percent decoded segments, which may not be valid UTF-8.
+  pub segments: Vec<Vec<u8>>,
+}
+
+fn segment_bytes(
+  min_octets: usize,
+  max_octets: usize,
+  obs_text: bool,
+) -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+  proptest::collection::vec(
+    safe_and_percent_encoded_octet(&PATH_SAFE_CHARS, obs_text),
+    min_octets..max_octets,
+  )
+  .prop_map(url_octets_to_bytes)
+}
+
+/// absolute path with no query params and no fragment as octets.
+///
+/// percent encoded octets are not restricted to UTF-8 sequences.
+///
+/// # Arguments
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `obs_text`: whether raw `obs-text` octets (`0x80` to `0xFF`) may appear in the path, RFC
+///   3986 forbids them but they are found in real traffic.
+///
+/// # Returns
+/// [`PathBytes`] and its raw representation.
+pub fn path_absolute_bytes(
+  max_segments: NonZero<usize>,
+  obs_text: bool,
+) -> impl Strategy<Value = (PathBytes, Vec<u8>)> {
+  (
+    segment_bytes(1, 50, obs_text),
+    proptest::collection::vec(segment_bytes(0, 50, obs_text), 0..=max_segments.get()),
+  )
+    .prop_map(|(segment_nz, segments)| {
+      let (segments, segment_reprs): (Vec<_>, Vec<_>) =
+        std::iter::once(segment_nz).chain(segments).unzip();
+      let mut repr = Vec::new();
+      for segment_repr in segment_reprs {
+        repr.push(b'/');
+        repr.extend_from_slice(&segment_repr);
+      }
+
+      (PathBytes { segments }, repr)
+    })
+}
+
+fn dot_segment() -> impl Strategy<Value = String> {
+  prop_oneof![
+    Just(".".to_string()),
+    Just("..".to_string()),
+    "%2(e|E)",
+    "(%2(e|E)|\\.)(%2(e|E)|\\.)",
+  ]
+}
+
+/// segment with leading or trailing dots that is not a dot segment once decoded.
+fn dot_lookalike_segment() -> impl Strategy<Value = String> {
+  // a `"."` inner segment would turn `".{segment}"` into a real `".."` segment.
+  let inner = || {
+    segment_nz(10, PercentEncoding::default()).prop_filter("dot segment", |segment| {
+      !matches!(decode_unreserved(segment).as_str(), "." | "..")
+    })
+  };
+  prop_oneof![
+    Just("...".to_string()),
+    inner().prop_map(|segment| format!("{segment}.")),
+    inner().prop_map(|segment| format!("{segment}..")),
+    inner().prop_map(|segment| format!(".{segment}")),
+    inner().prop_map(|segment| format!("{segment}%2e")),
+  ]
+}
+
+/// absolute path stressing dot segments handling.
+///
+/// unlike [`path_absolute`], dot segments (`"."`, `".."`, `"%2e"`, `"%2E%2e"`, ...), empty
+/// segments (`"//"`) and segments with leading or trailing dots are deliberately injected.
+///
+/// the normalized path is computed following RFC 3986 section 6.2.2: percent encoded unreserved
+/// characters are decoded, other percent encoded octets are upper cased, then dot segments are
+/// removed.
+///
+/// # Returns
+/// [`Path`] and its raw representation.
+pub fn path_dot_segments(max_segments: NonZero<usize>) -> impl Strategy<Value = (Path, String)> {
+  proptest::collection::vec(
+    prop_oneof![
+      4 => dot_segment(),
+      2 => Just(String::new()),
+      2 => dot_lookalike_segment(),
+      2 => segment(0, 20, PercentEncoding::default()),
+    ],
+    1..=max_segments.get(),
+  )
+  .prop_map(|segments| {
+    let repr = format!("/{}", segments.join("/"));
+    (Path { normalized: remove_dot_segments(&canonical_percent_encoding(&repr)) }, repr)
+  })
+}
+
+/// overlong UTF-8 encodings of `'/'` and `'\'` that lenient decoders turn into separators.
+const OVERLONG_SEPARATORS: [&[u8]; 3] = [&[0xc0, 0xaf], &[0xc1, 0x9c], &[0xe0, 0x80, 0xaf]];
+
+/// path traversal segment.
+#[derive(Debug, Clone)]
+pub struct TraversalSegment {
+  /// segment as it appears in the path.
+  pub raw: String,
+  /// segment octets after a single percent decoding pass.
+  pub decoded: Vec<u8>,
+  /// whether the segment escapes its boundaries once decoded: it holds a `'/'` or `'\'`
+  /// (including their overlong UTF-8 forms) or a NUL octet, or it becomes a `".."` segment,
+  /// possibly after decoding it more than once.
+  pub crosses_boundary: bool,
+}
+
+/// path crafted to exercise path traversal defences.
+#[derive(Debug, Clone)]
+pub struct TraversalPath {
+  pub segments: Vec<TraversalSegment>,
+}
+
+fn crosses_boundary(decoded: &[u8]) -> bool {
+  let mut current = decoded.to_vec();
+  loop {
+    if current == b".."
+      || current.iter().any(|b| matches!(b, b'/' | b'\\' | b'\0'))
+      || OVERLONG_SEPARATORS
+        .iter()
+        .any(|overlong| current.windows(overlong.len()).any(|window| window == *overlong))
+    {
+      return true;
+    }
+
+    let next = percent_decode(&current);
+    if next == current {
+      return false;
+    }
+    current = next;
+  }
+}
+
+fn traversal_segment() -> impl Strategy<Value = String> {
+  let dots = || prop_oneof![Just(".."), Just("%2e%2e"), Just("%2E%2e"), Just(".%2e")];
+  let separator = || {
+    prop_oneof![
+      Just("%2f"),
+      Just("%2F"),
+      Just("%5c"),
+      Just("%5C"),
+      Just("%c0%af"),
+      Just("%C0%AF"),
+      Just("%c1%9c"),
+      Just("%e0%80%af"),
+      Just("%252f"),
+      Just("%255c"),
+    ]
+  };
+
+  prop_oneof![
+    3 => segment_nz(10, PercentEncoding::default()),
+    2 => dots().prop_map(str::to_string),
+    // `..%2f..%2fetc%2fpasswd` like segments.
+    3 => (proptest::collection::vec(dots(), 1..=3), separator(), segment_nz(10, PercentEncoding::default())).prop_map(
+      |(dots, separator, segment)| format!("{}{separator}{segment}", dots.join(separator))
+    ),
+    // `%252e%252e` like segments.
+    2 => prop_oneof![Just("%252e%252e"), Just("%252E%252E"), Just("%25252e%25252e")]
+      .prop_map(str::to_string),
+    // `file.txt%00.png` like segments.
+    2 => (segment_nz(10, PercentEncoding::default()), "%00|%2500", segment_nz(10, PercentEncoding::default()))
+      .prop_map(|(name, nul, extension)| format!("{name}{nul}{extension}")),
+    // separators hidden in otherwise regular segments.
+    2 => (segment_nz(10, PercentEncoding::default()), separator(), segment_nz(10, PercentEncoding::default()))
+      .prop_map(|(start, separator, end)| format!("{start}{separator}{end}")),
+  ]
+}
+
+/// absolute path crafted to exercise path traversal defences.
+///
+/// segments deliberately include encoded separators (`"%2F"`, `"%5C"`), NUL octets (`"%00"`),
+/// overlong UTF-8 separators (`"%c0%af"`) and double encodings (`"%252e%252e"`).
+///
+/// # Returns
+/// [`TraversalPath`] and its raw representation.
+pub fn path_traversal(
+  max_segments: NonZero<usize>,
+) -> impl Strategy<Value = (TraversalPath, String)> {
+  proptest::collection::vec(traversal_segment(), 1..=max_segments.get()).prop_map(|segments| {
+    let repr = format!("/{}", segments.join("/"));
+    let segments = segments
+      .into_iter()
+      .map(|raw| {
+        let decoded = percent_decode(raw.as_bytes());
+        TraversalSegment { crosses_boundary: crosses_boundary(&decoded), decoded, raw }
+      })
+      .collect();
+
+    (TraversalPath { segments }, repr)
+  })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use std::num::NonZeroUsize;
+
+  use claims::assert_ok;
+  use proptest::{
+    prelude::{Just, any},
+    proptest,
+  };
+  use url::Url;
+
+  use super::*;
+  use crate::request_line::target::components::percent_encoding;
+
+  /// assert the percent decoded segments of the path representation are the model ones.
+  pub(crate) fn path_bytes_asserts(path: &PathBytes, repr: &[u8]) {
+    assert!(repr.starts_with(b"/"), "path should start with '/' but got {repr:?}");
+    let segments = repr[1..].split(|b| *b == b'/').map(percent_decode).collect::<Vec<_>>();
+    assert_eq!(path.segments, segments, "expected segments {:?} in {repr:?}", path.segments);
+  }
+
+  /// single percent decoding pass, independent of [`percent_decode`].
+  fn decode_once(raw: &str) -> Vec<u8> {
+    let mut parts = raw.as_bytes().split(|b| *b == b'%');
+    let mut decoded = parts.next().unwrap().to_vec();
+    for part in parts {
+      match part.get(..2).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+        Some(hex) => {
+          decoded.push(u8::from_str_radix(str::from_utf8(hex).unwrap(), 16).unwrap());
+          decoded.extend_from_slice(&part[2..]);
+        }
+        None => {
+          decoded.push(b'%');
+          decoded.extend_from_slice(part);
+        }
+      }
+    }
+    decoded
+  }
+
+  #[test]
+  fn crosses_boundary_works() {
+    for (raw, expected) in [
+      ("..", true),
+      ("%2e%2e", true),
+      ("%2E%2e", true),
+      (".%2e", true),
+      ("%252e%252e", true),
+      ("%25252e%25252e", true),
+      ("%2f", true),
+      ("%5C", true),
+      ("%252f", true),
+      ("%c0%af", true),
+      ("%C1%9C", true),
+      ("%e0%80%af", true),
+      ("%00", true),
+      ("file%2500.png", true),
+      ("..%2fetc", true),
+      ("a", false),
+      (".", false),
+      ("%2e", false),
+      ("...", false),
+      ("a.b", false),
+      ("..a", false),
+      ("%2e%2e%2e", false),
+      ("a%252e%252e", false),
+      ("%c0%ae", false),
+      ("%c0", false),
+      ("%25", false),
+      ("%2", false),
+      ("%zz", false),
+    ] {
+      assert_eq!(
+        crosses_boundary(&percent_decode(raw.as_bytes())),
+        expected,
+        "unexpected boundary crossing for {raw:?}"
+      );
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn path_absolute_works((_, repr) in path_absolute(NonZeroUsize::new(25).unwrap())) {
+      assert!(repr.starts_with('/'));
+    }
+
+    #[test]
+    fn dot_lookalike_segment_works(segment in dot_lookalike_segment()) {
+      let decoded = decode_unreserved(&segment);
+      assert!(decoded != "." && decoded != "..", "{segment:?} should not be a dot segment");
+      assert!(decoded.starts_with('.') || decoded.ends_with('.'), "{segment:?} should look like one");
+    }
+
+    #[test]
+    fn path_dot_segments_works((path, repr) in path_dot_segments(NonZeroUsize::new(25).unwrap())) {
+      assert!(repr.starts_with('/'));
+      assert!(path.normalized.starts_with('/'), "normalized path should be absolute but got {:?}", path.normalized);
+      assert!(
+        path.normalized.split('/').all(|segment| segment != "." && segment != ".."),
+        "normalized path should not contain dot segments but got {:?}",
+        path.normalized
+      );
+      assert!(
+        path.normalized.split('%').skip(1).all(|rest| !rest.chars().take(2).any(|c| c.is_ascii_lowercase())),
+        "normalized path should use upper case hexadecimal digits but got {:?}",
+        path.normalized
+      );
+
+      // the `url` crate never pops Windows drive letters (like `"c:"`) from the path.
+      let has_drive_letter = repr.split('/').any(|segment| {
+        segment.len() == 2 && segment.starts_with(|c: char| c.is_ascii_alphabetic()) && segment.ends_with(':')
+      });
+      if !has_drive_letter {
+        // the `url` crate removes percent encoded dot segments too, but keeps the remaining
+        // percent encoded octets as they are.
+        let url = assert_ok!(Url::parse(&format!("http://example.com{repr}")));
+        assert_eq!(path.normalized, canonical_percent_encoding(url.path()), "unexpected normalized path for {repr:?}");
+      }
+    }
+
+    #[test]
+    fn path_traversal_works((path, repr) in path_traversal(NonZeroUsize::new(25).unwrap())) {
+      assert!(repr.is_ascii(), "path should only contain ASCII characters but got {repr:?}");
+      let raw_segments = repr.strip_prefix('/').unwrap().split('/').collect::<Vec<_>>();
+      assert_eq!(raw_segments.len(), path.segments.len(), "segments should not contain literal separators in {repr:?}");
+
+      for (raw, segment) in raw_segments.into_iter().zip(&path.segments) {
+        assert_eq!(raw, segment.raw);
+        assert_eq!(segment.decoded, decode_once(raw), "unexpected decoded segment for {raw:?}");
+
+        let lowercase_raw = raw.to_ascii_lowercase();
+        if ["%2f", "%5c", "%00", "%c0%af", "%c1%9c"].iter().any(|encoded| lowercase_raw.contains(encoded)) {
+          assert!(segment.crosses_boundary, "segment {raw:?} should cross its boundary");
+        }
+        if raw.chars().all(|c| UNRESERVED.contains(&c)) && raw != ".." {
+          assert!(!segment.crosses_boundary, "segment {raw:?} should not cross its boundary");
+        }
+      }
+    }
+
+    #[test]
+    fn path_absolute_with_encoding_works(
+      (encoding, (path, repr)) in percent_encoding().prop_flat_map(|encoding| {
+        (Just(encoding), path_absolute_with_encoding(NonZeroUsize::new(25).unwrap(), encoding))
+      })
+    ) {
+      assert!(repr.starts_with('/'));
+      super::super::tests::percent_encoding_asserts(encoding, &repr, &path.normalized);
+      assert!(
+        path.normalized.split('/').all(|segment| segment != "." && segment != ".."),
+        "normalized path should not contain dot segments but got {:?}",
+        path.normalized
+      );
+    }
+
+    #[test]
+    fn path_absolute_bytes_works(
+      (obs_text, (path, repr)) in any::<bool>().prop_flat_map(|obs_text| {
+        (Just(obs_text), path_absolute_bytes(NonZeroUsize::new(25).unwrap(), obs_text))
+      })
+    ) {
+      path_bytes_asserts(&path, &repr);
+      if !obs_text {
+        assert!(repr.is_ascii(), "expected ASCII path but got {repr:?}");
+      }
+    }
+  }
+}
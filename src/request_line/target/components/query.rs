@@ -0,0 +1,259 @@
+//! URL query strategies.
+
+use std::{ops::RangeInclusive, sync::LazyLock};
+
+use array_concat::{concat_arrays, concat_arrays_size};
+use proptest::prelude::Strategy;
+
+use crate::request_line::target::components::{
+  PercentEncoding, UNRESERVED, UrlChar, UrlOctet, canonical_percent_encoding, char_diff_intervals,
+  safe_and_percent_encoded_char, safe_and_percent_encoded_octet, url_chars_to_string,
+  url_octets_to_bytes,
+};
+
+static QUERY_UNSAFE_CHARS: LazyLock<Vec<RangeInclusive<char>>> =
+  LazyLock::new(|| char_diff_intervals(&QUERY_SAFE_CHARS));
+
+// space character is included in safe chars, because it should be replaced with
+// '+' and not percent-encoded.
+// even if RFC 3986 states that sub-delims can be part of a query, it also states
+// that all reserved characters should be percent encoded. the safe option is to
+// percent encode all reserved characters.
+const QUERY_SAFE_CHARS: [char; concat_arrays_size!(UNRESERVED) + 5] =
+  concat_arrays!(UNRESERVED, [':', '@', '/', '?', ' ']);
+
+fn chars(encoding: PercentEncoding) -> impl Strategy<Value = UrlChar> {
+  safe_and_percent_encoded_char(&QUERY_SAFE_CHARS, &QUERY_UNSAFE_CHARS, encoding).prop_map(|c| {
+    if let UrlChar::Normal(c) = c
+      && c == ' '
+    {
+      // url-encoding requires space to be encoded as '+' instead of percent encoding
+      UrlChar::Normal('+')
+    } else {
+      c
+    }
+  })
+}
+
+fn query_subcomponent(
+  min_chars: usize,
+  max_chars: usize,
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = String> {
+  proptest::collection::vec(chars(encoding), min_chars..=max_chars).prop_map(url_chars_to_string)
+}
+
+/// URL Query parameter.
+#[derive(Debug, Clone)]
+pub struct QueryParam {
+  /// param key.
+  pub key: String,
+  /// param value.
+  pub value: Option<String>,
+}
+
+fn query_param_with(
+  encoding: PercentEncoding,
+  canonicalize: fn(&str) -> String,
+) -> impl Strategy<Value = (QueryParam, String)> {
+  (query_subcomponent(0, 50, encoding), query_subcomponent(0, 50, encoding)).prop_map(
+    move |(key, value)| {
+      let repr = format!("{key}={value}");
+      let value = if value.is_empty() { None } else { Some(canonicalize(&value)) };
+      (QueryParam { key: canonicalize(&key), value }, repr)
+    },
+  )
+}
+
+fn query_with(
+  min_queries: usize,
+  max_queries: usize,
+  encoding: PercentEncoding,
+  canonicalize: fn(&str) -> String,
+) -> impl Strategy<Value = (Vec<QueryParam>, String)> {
+  proptest::collection::vec(query_param_with(encoding, canonicalize), min_queries..=max_queries)
+    .prop_map(|params| {
+      let (params, reprs): (Vec<_>, Vec<_>) = params.into_iter().unzip();
+      (params, reprs.join("&"))
+    })
+}
+
+/// single URL query param
+/// # Returns
+/// [`QueryParam`] with it representation in the form `<key>=<value>`.
+pub fn query_param() -> impl Strategy<Value = (QueryParam, String)> {
+  query_param_with(PercentEncoding::default(), str::to_string)
+}
+
+/// single URL query param, percent encoded following `encoding`.
+///
+/// # Returns
+/// [`QueryParam`] in canonical form (percent encoded unreserved characters are decoded and the
+/// remaining percent encodings are upper cased) with it representation in the form
+/// `<key>=<value>`.
+pub fn query_param_with_encoding(
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = (QueryParam, String)> {
+  query_param_with(encoding, canonical_percent_encoding)
+}
+
+/// URL query.
+///
+/// # Returns
+/// Vec of [`QueryParam`] and it representation. individual params are separated by `'&'`.
+pub fn query(
+  min_queries: usize,
+  max_queries: usize,
+) -> impl Strategy<Value = (Vec<QueryParam>, String)> {
+  query_with(min_queries, max_queries, PercentEncoding::default(), str::to_string)
+}
+
+/// URL query, percent encoded following `encoding`.
+///
+/// # Returns
+/// Vec of [`QueryParam`] in canonical form and it representation. individual params are
+/// separated by `'&'`.
+pub fn query_with_encoding(
+  min_queries: usize,
+  max_queries: usize,
+  encoding: PercentEncoding,
+) -> impl Strategy<Value = (Vec<QueryParam>, String)> {
+  query_with(min_queries, max_queries, encoding, canonical_percent_encoding)
+}
+
+/// URL query parameter octets.
+#[derive(Debug, Clone)]
+pub struct QueryParamBytes {
+  /// percent decoded param key.
+  pub key: Vec<u8>,
+  /// percent decoded param value.
+  pub value: Option<Vec<u8>>,
+}
+
+fn query_subcomponent_bytes(
+  min_octets: usize,
+  max_octets: usize,
+  obs_text: bool,
+) -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+  proptest::collection::vec(
+    safe_and_percent_encoded_octet(&QUERY_SAFE_CHARS, obs_text).prop_map(|octet| match octet {
+      // url-encoding requires space to be encoded as '+' instead of percent encoding
+      UrlOctet::Normal(b' ') => UrlOctet::Normal(b'+'),
+      octet => octet,
+    }),
+    min_octets..=max_octets,
+  )
+  .prop_map(url_octets_to_bytes)
+}
+
+/// URL query as octets.
+///
+/// percent encoded octets are not restricted to UTF-8 sequences.
+///
+/// # Arguments
+/// * `min_queries`: minimum number of query params.
+/// * `max_queries`: maximum number of query params.
+/// * `obs_text`: whether raw `obs-text` octets (`0x80` to `0xFF`) may appear in the query, RFC
+///   3986 forbids them but they are found in real traffic.
+///
+/// # Returns
+/// Vec of [`QueryParamBytes`] and it representation. individual params are separated by `'&'`.
+pub fn query_bytes(
+  min_queries: usize,
+  max_queries: usize,
+  obs_text: bool,
+) -> impl Strategy<Value = (Vec<QueryParamBytes>, Vec<u8>)> {
+  proptest::collection::vec(
+    (query_subcomponent_bytes(0, 50, obs_text), query_subcomponent_bytes(0, 50, obs_text)),
+    min_queries..=max_queries,
+  )
+  .prop_map(|params| {
+    let mut reprs = Vec::with_capacity(params.len());
+    let params = params
+      .into_iter()
+      .map(|((key, key_repr), (value, value_repr))| {
+        reprs.push([key_repr, value_repr].join(&b'='));
+        QueryParamBytes { key, value: if value.is_empty() { None } else { Some(value) } }
+      })
+      .collect();
+
+    (params, reprs.join(&b'&'))
+  })
+}
+
+/// `application/x-www-form-urlencoded` name or value octets, with the query safe characters.
+///
+/// unlike in queries, spaces are decoded from `+`.
+///
+/// # Returns
+/// decoded octets and their representation.
+pub(crate) fn form_urlencoded_octets(
+  min_octets: usize,
+  max_octets: usize,
+) -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+  proptest::collection::vec(
+    safe_and_percent_encoded_octet(&QUERY_SAFE_CHARS, false),
+    min_octets..=max_octets,
+  )
+  .prop_map(|octets| {
+    let (decoded, mut repr) = url_octets_to_bytes(octets);
+    // spaces of the representation are the safe ones, encoded ones are `%20`.
+    for octet in repr.iter_mut().filter(|octet| **octet == b' ') {
+      *octet = b'+';
+    }
+    (decoded, repr)
+  })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::{prelude::Just, proptest};
+
+  use super::*;
+  use crate::request_line::target::components::{percent_decode, percent_encoding};
+
+  /// assert the percent decoded params of the query representation are the model ones.
+  pub(crate) fn query_bytes_asserts(params: &[QueryParamBytes], repr: &[u8]) {
+    let reprs =
+      if repr.is_empty() { vec![] } else { repr.split(|b| *b == b'&').collect::<Vec<_>>() };
+    assert_eq!(params.len(), reprs.len(), "expected {} params in {repr:?}", params.len());
+    for (param, repr) in params.iter().zip(reprs) {
+      let mut repr = repr.splitn(2, |b| *b == b'=');
+      assert_eq!(param.key, percent_decode(repr.next().unwrap()));
+      assert_eq!(param.value.clone().unwrap_or_default(), percent_decode(repr.next().unwrap()));
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn query_param_works((param, repr) in query_param()) {
+      println!("{repr:?}");
+      assert!(repr.starts_with(param.key.as_str()), "param should start with key but got {param:?} {repr:?}");
+      assert!(repr.ends_with(param.value.as_deref().unwrap_or_default()), "param should end with value but got {param:?} {repr:?}");
+    }
+
+    #[test]
+    fn query_with_encoding_works(
+      (encoding, (params, repr)) in percent_encoding().prop_flat_map(|encoding| {
+        (Just(encoding), query_with_encoding(0, 20, encoding))
+      })
+    ) {
+      let reprs = if repr.is_empty() { vec![] } else { repr.split('&').collect::<Vec<_>>() };
+      assert_eq!(params.len(), reprs.len(), "expected {} params in {repr:?}", params.len());
+      for (param, repr) in params.iter().zip(reprs) {
+        let (key_repr, value_repr) = repr.split_once('=').unwrap();
+        super::super::tests::percent_encoding_asserts(encoding, key_repr, &param.key);
+        super::super::tests::percent_encoding_asserts(
+          encoding,
+          value_repr,
+          param.value.as_deref().unwrap_or_default(),
+        );
+      }
+    }
+
+    #[test]
+    fn query_bytes_works((params, repr) in query_bytes(0, 20, true)) {
+      query_bytes_asserts(&params, &repr);
+    }
+  }
+}
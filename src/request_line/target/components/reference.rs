@@ -0,0 +1,459 @@
+//! URI reference and relative reference resolution (RFC 3986 section 5) strategies.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  option::of,
+  prelude::{Just, Strategy},
+  prop_oneof,
+  sample::select,
+};
+
+use crate::request_line::target::components::{
+  authority::{Authority, authority},
+  fragment::fragment,
+  path::{Path, path_absolute, path_rootless, remove_dot_segments},
+  query::{QueryParam, query},
+  scheme::http_scheme,
+  uri::{HierPart, Uri, uri},
+};
+
+/// relative reference's relative part.
+///
+/// > `relative-part = "//" authority path-abempty / path-absolute / path-noscheme / path-empty`
+#[derive(Debug, Clone)]
+pub enum RelativePart {
+  /// network-path reference, like `//example.com/foo`.
+  NetworkPath { authority: Authority, path: Option<Path> },
+  /// absolute-path reference, like `/foo/bar`.
+  AbsolutePath(Path),
+  /// relative-path reference, like `../foo/bar`.
+  ///
+  /// the path is kept raw, its dot segments only make sense once merged with the base path.
+  RelativePath(String),
+  /// empty path, the reference is only made of a query and/or a fragment, like `?foo` or `#bar`.
+  Empty,
+}
+
+/// relative reference components.
+#[derive(Debug, Clone)]
+pub struct RelativeRef {
+  pub relative_part: RelativePart,
+  pub query: Option<Vec<QueryParam>>,
+  pub fragment: Option<String>,
+}
+
+/// URI reference, either a URI or a relative reference.
+#[derive(Debug, Clone)]
+pub enum UriReference {
+  Uri(Uri),
+  Relative(RelativeRef),
+}
+
+/// relative reference resolution.
+#[derive(Debug, Clone)]
+pub struct ReferenceResolution {
+  /// base URI, its path is free of dot segments.
+  pub base: Uri,
+  /// base URI representation.
+  pub base_repr: String,
+  /// relative reference to resolve against `base`.
+  pub reference: RelativeRef,
+  /// expected target URI after resolving `reference` against `base` as described in
+  /// RFC 3986 section 5.2.
+  pub resolved: String,
+}
+
+/// raw URI components as defined in RFC 3986 section 5.2.
+#[derive(Debug, Default)]
+struct RawComponents {
+  scheme: Option<String>,
+  authority: Option<String>,
+  path: String,
+  query: Option<String>,
+  fragment: Option<String>,
+}
+
+impl RawComponents {
+  /// recomposes components into a URI reference following RFC 3986 section 5.3.
+  fn recompose(&self) -> String {
+    let mut result = String::new();
+    if let Some(scheme) = self.scheme.as_deref() {
+      result.push_str(scheme);
+      result.push(':');
+    }
+    if let Some(authority) = self.authority.as_deref() {
+      result.push_str("//");
+      result.push_str(authority);
+    }
+    result.push_str(&self.path);
+    if let Some(query) = self.query.as_deref() {
+      result.push('?');
+      result.push_str(query);
+    }
+    if let Some(fragment) = self.fragment.as_deref() {
+      result.push('#');
+      result.push_str(fragment);
+    }
+
+    result
+  }
+
+  /// resolves `reference` against `self` following RFC 3986 section 5.2.2.
+  fn resolve(&self, reference: &RawComponents) -> RawComponents {
+    let (authority, path, query) = if reference.authority.is_some() {
+      (reference.authority.clone(), remove_dot_segments(&reference.path), reference.query.clone())
+    } else if reference.path.is_empty() {
+      (
+        self.authority.clone(),
+        self.path.clone(),
+        reference.query.clone().or_else(|| self.query.clone()),
+      )
+    } else if reference.path.starts_with('/') {
+      (self.authority.clone(), remove_dot_segments(&reference.path), reference.query.clone())
+    } else {
+      (
+        self.authority.clone(),
+        remove_dot_segments(&self.merge(&reference.path)),
+        reference.query.clone(),
+      )
+    };
+
+    RawComponents {
+      scheme: self.scheme.clone(),
+      authority,
+      path,
+      query,
+      fragment: reference.fragment.clone(),
+    }
+  }
+
+  /// merges a relative path with the base path following RFC 3986 section 5.2.3.
+  fn merge(&self, reference_path: &str) -> String {
+    if self.authority.is_some() && self.path.is_empty() {
+      format!("/{reference_path}")
+    } else {
+      match self.path.rfind('/') {
+        Some(last_slash) => format!("{}{reference_path}", &self.path[..=last_slash]),
+        None => reference_path.to_string(),
+      }
+    }
+  }
+}
+
+fn query_repr(query: Option<&(Vec<QueryParam>, String)>) -> Option<String> {
+  query.map(|(_, repr)| repr.clone())
+}
+
+fn relative_part(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+) -> impl Strategy<Value = (RelativePart, Option<String>, String)> {
+  prop_oneof![
+    (authority(max_label_count), of(path_absolute(max_segments))).prop_map(
+      |((authority, authority_repr), path)| {
+        let (path, path_repr) = path.unzip();
+        (
+          RelativePart::NetworkPath { authority, path },
+          Some(authority_repr),
+          path_repr.unwrap_or_default(),
+        )
+      }
+    ),
+    path_absolute(max_segments).prop_map(|(path, repr)| (
+      RelativePart::AbsolutePath(path),
+      None,
+      repr
+    )),
+    (proptest::collection::vec(select(&["..", "."][..]), 0..=4), of(path_rootless(max_segments)))
+      .prop_map(|(dot_segments, path)| {
+        let mut segments = dot_segments.into_iter().map(str::to_string).collect::<Vec<_>>();
+        if let Some((_, path_repr)) = path {
+          // a colon in the first segment would be mistaken for a scheme, so the segment must be
+          // preceded by a dot segment.
+          if segments.is_empty()
+            && path_repr.split('/').next().is_some_and(|segment| segment.contains(':'))
+          {
+            segments.push(".".to_string());
+          }
+          segments.push(path_repr);
+        }
+        if segments.is_empty() {
+          segments.push("..".to_string());
+        }
+        let repr = segments.join("/");
+        (RelativePart::RelativePath(repr.clone()), None, repr)
+      }),
+    Just((RelativePart::Empty, None, String::new())),
+  ]
+}
+
+fn relative_ref_components(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (RelativeRef, RawComponents)> {
+  (
+    relative_part(max_label_count, max_segments),
+    of(query(*query_count_range.start(), *query_count_range.end())),
+    of(fragment()),
+  )
+    .prop_map(|((relative_part, authority, path), query, fragment)| {
+      let raw = RawComponents {
+        scheme: None,
+        authority,
+        path,
+        query: query_repr(query.as_ref()),
+        fragment: fragment.clone(),
+      };
+      (RelativeRef { relative_part, query: query.map(|q| q.0), fragment }, raw)
+    })
+}
+
+fn base_components(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Uri, RawComponents)> {
+  (
+    http_scheme(),
+    authority(max_label_count),
+    of(path_absolute(max_segments)),
+    of(query(*query_count_range.start(), *query_count_range.end())),
+  )
+    .prop_map(|(scheme, (authority, authority_repr), path, query)| {
+      // the normalized path is used as is, so the base URI is free of dot segments.
+      let path = path.map(|(path, _)| path);
+      let raw = RawComponents {
+        scheme: Some(scheme.clone()),
+        authority: Some(authority_repr),
+        path: path.as_ref().map(|path| path.normalized.clone()).unwrap_or_default(),
+        query: query_repr(query.as_ref()),
+        fragment: None,
+      };
+      let uri = Uri {
+        scheme,
+        hier_part: HierPart::Authority { authority: Some(authority), path },
+        query: query.map(|q| q.0),
+        fragment: None,
+      };
+      (uri, raw)
+    })
+}
+
+/// strategy for generating relative reference.
+///
+/// relative reference has the following format: `<relative-part>[?<query>][#<fragment>]`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`RelativeRef`] and its representation.
+pub fn relative_ref(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (RelativeRef, String)> {
+  relative_ref_components(max_label_count, max_segments, query_count_range)
+    .prop_map(|(reference, raw)| (reference, raw.recompose()))
+}
+
+/// strategy for generating URI reference.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`UriReference`] and its representation.
+pub fn uri_reference(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (UriReference, String)> {
+  prop_oneof![
+    uri(max_label_count, max_segments, query_count_range.clone())
+      .prop_map(|(uri, repr)| (UriReference::Uri(uri), repr)),
+    relative_ref(max_label_count, max_segments, query_count_range)
+      .prop_map(|(reference, repr)| (UriReference::Relative(reference), repr)),
+  ]
+}
+
+/// strategy for generating a base URI, a relative reference and the expected target URI.
+///
+/// the base URI uses the HTTP scheme, while the relative reference covers network-path,
+/// absolute-path, relative-path (with leading `"../"` and `"./"` segments), query-only and
+/// fragment-only references.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the paths.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`ReferenceResolution`] and the relative reference representation.
+pub fn reference_resolution(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (ReferenceResolution, String)> {
+  (
+    base_components(max_label_count, max_segments, query_count_range.clone()),
+    relative_ref_components(max_label_count, max_segments, query_count_range),
+  )
+    .prop_map(|((base, base_raw), (reference, reference_raw))| {
+      let resolved = base_raw.resolve(&reference_raw).recompose();
+      (
+        ReferenceResolution { base, base_repr: base_raw.recompose(), reference, resolved },
+        reference_raw.recompose(),
+      )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use claims::{assert_err, assert_ok};
+  use proptest::proptest;
+  use url::Url;
+
+  use super::*;
+
+  /// splits a URI reference into its components following RFC 3986 appendix B.
+  fn parse(reference: &str) -> RawComponents {
+    let (rest, fragment) = match reference.split_once('#') {
+      Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+      None => (reference, None),
+    };
+    let (rest, query) = match rest.split_once('?') {
+      Some((rest, query)) => (rest, Some(query.to_string())),
+      None => (rest, None),
+    };
+    let (scheme, rest) = match rest.split_once(':') {
+      Some((scheme, rest)) if !scheme.contains('/') => (Some(scheme.to_string()), rest),
+      _ => (None, rest),
+    };
+    let (authority, path) = match rest.strip_prefix("//") {
+      Some(rest) => {
+        let path_start = rest.find('/').unwrap_or(rest.len());
+        (Some(rest[..path_start].to_string()), rest[path_start..].to_string())
+      }
+      None => (None, rest.to_string()),
+    };
+
+    RawComponents { scheme, authority, path, query, fragment }
+  }
+
+  #[test]
+  fn remove_dot_segments_works() {
+    for (path, expected) in [
+      ("/a/b/c/./../../g", "/a/g"),
+      ("mid/content=5/../6", "mid/6"),
+      ("/a/..", "/"),
+      ("/.", "/"),
+      ("/./a", "/a"),
+      ("../a", "a"),
+      ("./a/", "a/"),
+      ("..", ""),
+      ("/a//../b", "/a/b"),
+    ] {
+      assert_eq!(remove_dot_segments(path), expected, "unexpected result for {path:?}");
+    }
+  }
+
+  #[test]
+  fn resolve_works_on_rfc_examples() {
+    let base = parse("http://a/b/c/d;p?q");
+    for (reference, expected) in [
+      ("g", "http://a/b/c/g"),
+      ("./g", "http://a/b/c/g"),
+      ("g/", "http://a/b/c/g/"),
+      ("/g", "http://a/g"),
+      ("//g", "http://g"),
+      ("?y", "http://a/b/c/d;p?y"),
+      ("g?y", "http://a/b/c/g?y"),
+      ("#s", "http://a/b/c/d;p?q#s"),
+      ("g#s", "http://a/b/c/g#s"),
+      ("g?y#s", "http://a/b/c/g?y#s"),
+      (";x", "http://a/b/c/;x"),
+      ("g;x", "http://a/b/c/g;x"),
+      ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+      ("", "http://a/b/c/d;p?q"),
+      (".", "http://a/b/c/"),
+      ("./", "http://a/b/c/"),
+      ("..", "http://a/b/"),
+      ("../", "http://a/b/"),
+      ("../g", "http://a/b/g"),
+      ("../..", "http://a/"),
+      ("../../", "http://a/"),
+      ("../../g", "http://a/g"),
+      ("../../../g", "http://a/g"),
+      ("../../../../g", "http://a/g"),
+      ("/./g", "http://a/g"),
+      ("/../g", "http://a/g"),
+      ("g.", "http://a/b/c/g."),
+      (".g", "http://a/b/c/.g"),
+      ("g..", "http://a/b/c/g.."),
+      ("..g", "http://a/b/c/..g"),
+      ("./../g", "http://a/b/g"),
+      ("./g/.", "http://a/b/c/g/"),
+      ("g/./h", "http://a/b/c/g/h"),
+      ("g/../h", "http://a/b/c/h"),
+      ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+      ("g;x=1/../y", "http://a/b/c/y"),
+    ] {
+      let reference = parse(reference);
+      assert_eq!(
+        base.resolve(&reference).recompose(),
+        expected,
+        "unexpected result for {reference:?}"
+      );
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn relative_ref_works((reference, repr) in relative_ref(20, 50.try_into().unwrap(), 0..=20)) {
+      assert_err!(Url::parse(&repr), "relative reference should not be parsed without base but got {repr:?}");
+      match reference.relative_part {
+        RelativePart::NetworkPath { .. } => assert!(repr.starts_with("//"), "expected network path reference but got {repr:?}"),
+        RelativePart::AbsolutePath(_) => {
+          assert!(repr.starts_with('/') && !repr.starts_with("//"), "expected absolute path reference but got {repr:?}");
+        }
+        RelativePart::RelativePath(path) => {
+          assert!(repr.starts_with(&path), "expected relative path {path:?} in {repr:?}");
+          let first_segment = path.split('/').next().unwrap_or_default();
+          assert!(!first_segment.contains(':'), "first segment should not contain colon but got {repr:?}");
+        }
+        RelativePart::Empty => assert!(repr.is_empty() || repr.starts_with(['?', '#']), "expected empty path but got {repr:?}"),
+      }
+    }
+
+    #[test]
+    fn uri_reference_works((reference, repr) in uri_reference(20, 50.try_into().unwrap(), 0..=20)) {
+      match reference {
+        UriReference::Uri(uri) => assert!(repr.starts_with(&format!("{}:", uri.scheme))),
+        UriReference::Relative(_) => {
+          assert_err!(Url::parse(&repr));
+        }
+      }
+    }
+
+    #[test]
+    fn reference_resolution_works((resolution, repr) in reference_resolution(20, 50.try_into().unwrap(), 0..=20)) {
+      let base = assert_ok!(Url::parse(&resolution.base_repr), "should be good base URL but got {}", resolution.base_repr);
+      let joined = assert_ok!(base.join(&repr), "should resolve {repr:?} against {base}");
+      let resolved = assert_ok!(Url::parse(&resolution.resolved), "should be good URL but got {}", resolution.resolved);
+      assert_eq!(joined, resolved, "resolving {repr:?} against {base} gave {joined} but expected {}", resolution.resolved);
+
+      let resolved_path = parse(&resolution.resolved).path;
+      assert!(
+        resolved_path.split('/').all(|segment| segment != "." && segment != ".."),
+        "resolved path should not contain dot segments but got {resolved_path:?}"
+      );
+    }
+  }
+}
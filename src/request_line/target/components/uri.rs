@@ -0,0 +1,170 @@
+//! Generic URI (RFC 3986) strategies.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  option::of,
+  prelude::{Just, Strategy},
+  prop_oneof,
+};
+
+use crate::request_line::target::components::{
+  authority::{Authority, authority},
+  fragment::fragment,
+  path::{Path, path_absolute, path_rootless},
+  query::{QueryParam, query},
+  scheme::any_scheme,
+};
+
+/// URI hierarchical part.
+///
+/// > `hier-part = "//" authority path-abempty / path-absolute / path-rootless / path-empty`
+#[derive(Debug, Clone)]
+pub enum HierPart {
+  /// authority followed by an optional absolute path, like in `ftp://example.com/pub`.
+  ///
+  /// `authority` is `None` when the authority is empty, like in `file:///etc/hosts`.
+  Authority { authority: Option<Authority>, path: Option<Path> },
+  /// absolute path without authority, like in `news:/comp.lang`.
+  Absolute(Path),
+  /// rootless path, like in `mailto:user@example.com` or `urn:isbn:0451450523`.
+  Rootless(Path),
+  /// empty path, like in `about:`.
+  Empty,
+}
+
+/// generic URI components.
+#[derive(Debug, Clone)]
+pub struct Uri {
+  pub scheme: String,
+  pub hier_part: HierPart,
+  pub query: Option<Vec<QueryParam>>,
+  pub fragment: Option<String>,
+}
+
+/// strategy for generating URI hierarchical part.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+///
+/// # Returns
+/// [`HierPart`] and its representation.
+pub fn hier_part(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+) -> impl Strategy<Value = (HierPart, String)> {
+  prop_oneof![
+    (of(authority(max_label_count)), of(path_absolute(max_segments))).prop_map(
+      |(authority, path)| {
+        let repr = format!(
+          "//{authority}{path}",
+          authority = authority.as_ref().map(|(_, repr)| repr.as_str()).unwrap_or_default(),
+          path = path.as_ref().map(|(_, repr)| repr.as_str()).unwrap_or_default(),
+        );
+        (
+          HierPart::Authority {
+            authority: authority.map(|(authority, _)| authority),
+            path: path.map(|(path, _)| path),
+          },
+          repr,
+        )
+      }
+    ),
+    path_absolute(max_segments).prop_map(|(path, repr)| (HierPart::Absolute(path), repr)),
+    path_rootless(max_segments).prop_map(|(path, repr)| (HierPart::Rootless(path), repr)),
+    Just((HierPart::Empty, String::new())),
+  ]
+}
+
+/// strategy for generating generic URI with any scheme.
+///
+/// URI has the following format: `<scheme>:<hier-part>[?<query>][#<fragment>]`.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts.
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+///
+/// # Returns
+/// [`Uri`] and its representation.
+pub fn uri(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Uri, String)> {
+  (
+    any_scheme(),
+    hier_part(max_label_count, max_segments),
+    of(query(*query_count_range.start(), *query_count_range.end())),
+    of(fragment()),
+  )
+    .prop_map(|(scheme, (hier_part, hier_part_repr), query, fragment)| {
+      let repr = format!(
+        "{scheme}:{hier_part_repr}{query}{fragment}",
+        query = query.as_ref().map(|(_, query)| format!("?{query}")).as_deref().unwrap_or_default(),
+        fragment =
+          fragment.as_ref().map(|fragment| format!("#{fragment}")).as_deref().unwrap_or_default()
+      );
+
+      (Uri { scheme, hier_part, query: query.map(|q| q.0), fragment }, repr)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use claims::assert_ok;
+  use proptest::proptest;
+  use url::Url;
+
+  use super::*;
+
+  fn is_special_scheme(scheme: &str) -> bool {
+    matches!(scheme, "http" | "https" | "ws" | "wss" | "ftp" | "file")
+  }
+
+  proptest! {
+    #[test]
+    fn uri_works((uri, repr) in uri(20, 50.try_into().unwrap(), 0..=20)) {
+      assert!(repr.starts_with(&format!("{}:", uri.scheme)), "URI should start with its scheme but got {repr:?}");
+
+      let scheme = uri.scheme.to_ascii_lowercase();
+      // special schemes follow their own parsing rules in the `url` crate.
+      if !is_special_scheme(&scheme) {
+        let url = assert_ok!(Url::parse(&repr), "should be good URI but got {repr}");
+        assert_eq!(scheme, url.scheme());
+
+        match &uri.hier_part {
+          HierPart::Authority { authority, .. } => {
+            assert!(url.has_authority(), "expected authority in {repr:?}");
+            if let Some(authority) = authority {
+              assert_eq!(authority.port, url.port(), "expected port {:?} but parsed {:?}", authority.port, url.port());
+            }
+          }
+          HierPart::Absolute(_) => {
+            assert!(!url.has_authority(), "expected no authority in {repr:?}");
+            assert!(url.path().starts_with('/'), "expected absolute path but parsed {:?}", url.path());
+          }
+          HierPart::Rootless(_) | HierPart::Empty => {
+            assert!(url.cannot_be_a_base(), "expected opaque path in {repr:?}");
+          }
+        }
+
+        match (uri.query.as_deref(), url.query()) {
+          (None, None) => {}
+          (Some(query), Some(query2)) => {
+            let query = query
+              .iter()
+              .map(|query| format!("{}={}", query.key, query.value.as_deref().unwrap_or_default()))
+              .collect::<Vec<_>>()
+              .join("&");
+            assert_eq!(query, query2, "expected query {query:?} but parsed {query2:?}");
+          }
+          (query, query2) => panic!("expected query {query:?} but got {query2:?}"),
+        }
+
+        assert_eq!(uri.fragment.as_deref(), url.fragment());
+      }
+    }
+  }
+}
@@ -0,0 +1,228 @@
+//! HTTP request target strategies.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::{
+  prelude::{BoxedStrategy, Strategy},
+  prop_oneof,
+};
+
+use crate::request_line::{
+  target::{
+    absolute_form::{AbsoluteForm, AbsoluteFormBytes},
+    authority_form::AuthorityForm,
+    origin_form::{OriginForm, OriginFormBytes},
+  },
+  verb::Method,
+};
+
+pub mod absolute_form;
+pub mod asterisk_form;
+pub mod authority_form;
+pub mod components;
+pub mod origin_form;
+
+/// All valid HTTP request target forms.
+#[derive(Debug, Clone)]
+pub enum RequestTarget {
+  Absolute(AbsoluteForm),
+  Origin(OriginForm),
+  Authority(AuthorityForm),
+  Asterisk,
+}
+
+/// strategy for generating HTTP request target.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form,
+///   origin form and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of  absolute form
+///   and origin form.
+///
+/// # Returns
+/// [`RequestTarget`] and it representation.
+pub fn target(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (RequestTarget, String)> {
+  prop_oneof![
+    absolute_form::absolute(max_label_count, max_segments, query_count_range.clone())
+      .prop_map(|(absolute, repr)| (RequestTarget::Absolute(absolute), repr)),
+    origin_form::origin(max_segments, query_count_range)
+      .prop_map(|(origin, repr)| (RequestTarget::Origin(origin), repr)),
+    authority_form::authority(max_label_count)
+      .prop_map(|(authority, repr)| (RequestTarget::Authority(authority), repr)),
+    asterisk_form::asterisk().prop_map(|repr| (RequestTarget::Asterisk, repr)),
+  ]
+}
+
+/// strategy for generating HTTP request target in a form allowed for the method (RFC 9112
+/// section 3.2).
+///
+/// `CONNECT` only uses authority form. Other methods use origin or absolute form, and `OPTIONS`
+/// may also use asterisk form.
+///
+/// # Arguments
+/// * `method`: method of the request.
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form,
+///   origin form and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of  absolute form
+///   and origin form.
+///
+/// # Returns
+/// [`RequestTarget`] and it representation.
+pub fn target_for_method(
+  method: &Method,
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> BoxedStrategy<(RequestTarget, String)> {
+  let absolute = absolute_form::absolute(max_label_count, max_segments, query_count_range.clone())
+    .prop_map(|(absolute, repr)| (RequestTarget::Absolute(absolute), repr));
+  let origin = origin_form::origin(max_segments, query_count_range)
+    .prop_map(|(origin, repr)| (RequestTarget::Origin(origin), repr));
+  match method {
+    Method::Connect => authority_form::authority(max_label_count)
+      .prop_map(|(authority, repr)| (RequestTarget::Authority(authority), repr))
+      .boxed(),
+    Method::Options => prop_oneof![
+      absolute,
+      origin,
+      asterisk_form::asterisk().prop_map(|repr| (RequestTarget::Asterisk, repr)),
+    ]
+    .boxed(),
+    _ => prop_oneof![absolute, origin].boxed(),
+  }
+}
+
+/// All valid HTTP request target forms as octets.
+#[derive(Debug, Clone)]
+pub enum RequestTargetBytes {
+  Absolute(AbsoluteFormBytes),
+  Origin(OriginFormBytes),
+  Authority(AuthorityForm),
+  Asterisk,
+}
+
+/// strategy for generating HTTP request target as octets.
+///
+/// # Arguments
+/// * `max_label_count`: maximum label count to use for domain hosts in case of authority form
+///   and absolute form.
+/// * `max_segments`: maximum number of segments that compose the path in case of absolute form
+///   and origin form.
+/// * `query_count_range`: range of the number of queries to include in case of absolute form
+///   and origin form.
+/// * `obs_text`: whether raw `obs-text` octets (`0x80` to `0xFF`) may appear in paths and
+///   queries.
+///
+/// # Returns
+/// [`RequestTargetBytes`] and it representation.
+pub fn target_bytes(
+  max_label_count: usize,
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  obs_text: bool,
+) -> impl Strategy<Value = (RequestTargetBytes, Vec<u8>)> {
+  prop_oneof![
+    absolute_form::absolute_bytes(
+      max_label_count,
+      max_segments,
+      query_count_range.clone(),
+      obs_text
+    )
+    .prop_map(|(absolute, repr)| (RequestTargetBytes::Absolute(absolute), repr)),
+    origin_form::origin_bytes(max_segments, query_count_range, obs_text)
+      .prop_map(|(origin, repr)| (RequestTargetBytes::Origin(origin), repr)),
+    authority_form::authority(max_label_count)
+      .prop_map(|(authority, repr)| (RequestTargetBytes::Authority(authority), repr.into_bytes())),
+    asterisk_form::asterisk().prop_map(|repr| (RequestTargetBytes::Asterisk, repr.into_bytes())),
+  ]
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use proptest::{
+    prelude::{Just, any},
+    proptest,
+  };
+
+  use super::*;
+
+  /// assert the target form is allowed for the method.
+  pub(crate) fn target_form_asserts(method: &Method, target: &RequestTarget) {
+    match target {
+      RequestTarget::Authority(_) => assert_eq!(*method, Method::Connect),
+      RequestTarget::Asterisk => assert_eq!(*method, Method::Options),
+      RequestTarget::Absolute(_) | RequestTarget::Origin(_) => {
+        assert_ne!(*method, Method::Connect, "CONNECT requires authority form");
+      }
+    }
+  }
+
+  pub(in super::super) fn target_asserts(target: &RequestTarget, repr: &str) {
+    match target {
+      RequestTarget::Absolute(absolute_form) => {
+        absolute_form::tests::absolute_asserts(absolute_form, repr);
+      }
+      RequestTarget::Origin(origin_form) => origin_form::tests::origin_asserts(origin_form, repr),
+      RequestTarget::Authority(authority_form) => {
+        authority_form::tests::authority_asserts(authority_form, repr);
+      }
+      RequestTarget::Asterisk => asterisk_form::tests::asterisk_asserts(repr),
+    }
+  }
+
+  pub(in super::super) fn target_bytes_asserts(target: &RequestTargetBytes, repr: &[u8]) {
+    match target {
+      RequestTargetBytes::Absolute(absolute_form) => {
+        absolute_form::tests::absolute_bytes_asserts(absolute_form, repr);
+      }
+      RequestTargetBytes::Origin(origin_form) => {
+        origin_form::tests::origin_bytes_asserts(origin_form, repr);
+      }
+      // authority and asterisk forms are ASCII.
+      RequestTargetBytes::Authority(authority_form) => {
+        authority_form::tests::authority_asserts(authority_form, str::from_utf8(repr).unwrap());
+      }
+      RequestTargetBytes::Asterisk => {
+        asterisk_form::tests::asterisk_asserts(str::from_utf8(repr).unwrap());
+      }
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn target_works((target, repr) in target(20, 50.try_into().unwrap(), 0..=20)) {
+      target_asserts(&target, &repr);
+    }
+
+    #[test]
+    fn target_for_method_works(
+      ((method, _), (target, repr)) in crate::request_line::verb::method().prop_flat_map(|(method, repr)| {
+        let target = target_for_method(&method, 20, 50.try_into().unwrap(), 0..=20);
+        (Just((method, repr)), target)
+      })
+    ) {
+      target_form_asserts(&method, &target);
+      target_asserts(&target, &repr);
+    }
+
+    #[test]
+    fn target_bytes_works(
+      (obs_text, (target, repr)) in any::<bool>().prop_flat_map(|obs_text| {
+        (Just(obs_text), target_bytes(20, 50.try_into().unwrap(), 0..=20, obs_text))
+      })
+    ) {
+      if !obs_text {
+        assert!(repr.is_ascii(), "expected only ASCII octets without obs-text but got {repr:?}");
+      }
+      target_bytes_asserts(&target, &repr);
+    }
+  }
+}
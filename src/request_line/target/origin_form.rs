@@ -0,0 +1,136 @@
+//! HTTP request target in origin form strategies.
+
+use std::{num::NonZero, ops::RangeInclusive};
+
+use proptest::prelude::Strategy;
+
+use super::components::{
+  path::{Path, PathBytes, path_absolute, path_absolute_bytes},
+  query::{QueryParam, QueryParamBytes, query, query_bytes},
+};
+
+/// URL origin form components
+#[derive(Debug, Clone)]
+pub struct OriginForm {
+  pub path: Path,
+  pub query: Option<Vec<QueryParam>>,
+}
+
+/// strategy for generating target origin form.
+/// # Returns
+/// [`OriginForm`] and its representation.
+pub fn origin(
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+) -> impl Strategy<Value = (OriginForm, String)> {
+  (path_absolute(max_segments), query(*query_count_range.start(), *query_count_range.end()))
+    .prop_map(|((path, path_repr), (query, query_repr))| {
+      if query.is_empty() {
+        (OriginForm { path, query: None }, path_repr)
+      } else {
+        let repr = format!("{path_repr}?{query_repr}");
+        (OriginForm { path, query: Some(query) }, repr)
+      }
+    })
+}
+
+/// URL origin form components as octets.
+#[derive(Debug, Clone)]
+pub struct OriginFormBytes {
+  pub path: PathBytes,
+  pub query: Option<Vec<QueryParamBytes>>,
+}
+
+/// strategy for generating target origin form as octets.
+///
+/// # Arguments
+/// * `max_segments`: maximum number of segments that compose the path.
+/// * `query_count_range`: range of the number of queries to include.
+/// * `obs_text`: whether raw `obs-text` octets (`0x80` to `0xFF`) may appear in the target.
+///
+/// # Returns
+/// [`OriginFormBytes`] and its representation.
+pub fn origin_bytes(
+  max_segments: NonZero<usize>,
+  query_count_range: RangeInclusive<usize>,
+  obs_text: bool,
+) -> impl Strategy<Value = (OriginFormBytes, Vec<u8>)> {
+  (
+    path_absolute_bytes(max_segments, obs_text),
+    query_bytes(*query_count_range.start(), *query_count_range.end(), obs_text),
+  )
+    .prop_map(|((path, mut repr), (query, query_repr))| {
+      if query.is_empty() {
+        (OriginFormBytes { path, query: None }, repr)
+      } else {
+        repr.push(b'?');
+        repr.extend_from_slice(&query_repr);
+        (OriginFormBytes { path, query: Some(query) }, repr)
+      }
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+  use std::sync::LazyLock;
+
+  use claims::assert_ok;
+  use proptest::proptest;
+  use url::Url;
+
+  use super::*;
+  use crate::request_line::target::components::{
+    path::tests::path_bytes_asserts, query::tests::query_bytes_asserts,
+  };
+
+  const DUMMY_BASE_URL: &str = "https://example.com";
+  static BASE_URL: LazyLock<Url> = LazyLock::new(|| {
+    Url::parse(DUMMY_BASE_URL).unwrap_or_else(|_| panic!("{DUMMY_BASE_URL:?} is a valid base URL"))
+  });
+
+  pub(in super::super) fn origin_asserts(origin: &OriginForm, repr: &str) {
+    let url = assert_ok!(BASE_URL.join(repr));
+
+    assert_eq!(
+      origin.path.normalized,
+      url.path(),
+      "expected path {:?} but parsed {}",
+      origin.path.normalized,
+      url.path()
+    );
+
+    match (origin.query.as_deref(), url.query()) {
+      (None, None) => {}
+      (Some(query), Some(query2)) => {
+        let query = query
+          .iter()
+          .map(|query| format!("{}={}", query.key, query.value.as_deref().unwrap_or_default()))
+          .collect::<Vec<_>>()
+          .join("&");
+        assert_eq!(query, query2, "expected query {query:?} but parsed {query2:?}");
+      }
+      (query, query2) => panic!("expected query {query:?} but got {query2:?}"),
+    }
+  }
+
+  pub(in super::super) fn origin_bytes_asserts(origin: &OriginFormBytes, repr: &[u8]) {
+    let query_start = repr.iter().position(|b| *b == b'?');
+    assert_eq!(origin.query.is_some(), query_start.is_some(), "unexpected query in {repr:?}");
+    path_bytes_asserts(&origin.path, &repr[..query_start.unwrap_or(repr.len())]);
+    if let (Some(query), Some(query_start)) = (&origin.query, query_start) {
+      query_bytes_asserts(query, &repr[query_start + 1..]);
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn origin_works((origin, repr) in origin(50.try_into().unwrap(), 0..=20)) {
+      origin_asserts(&origin, &repr);
+    }
+
+    #[test]
+    fn origin_bytes_works((origin, repr) in origin_bytes(50.try_into().unwrap(), 0..=20, true)) {
+      origin_bytes_asserts(&origin, &repr);
+    }
+  }
+}
@@ -1,8 +1,16 @@
 //! HTTP request verb strategies.
 
-use proptest::{prelude::Strategy, prop_compose, prop_oneof};
+use std::fmt;
+
+use proptest::{
+  prelude::{Just, Strategy},
+  prop_compose, prop_oneof,
+  sample::select,
+};
 use rand::Rng;
 
+use crate::header::common::token;
+
 const GET_VERB: &str = "GET";
 const HEAD_VERB: &str = "HEAD";
 const POST_VERB: &str = "POST";
@@ -13,6 +21,157 @@ const OPTIONS_VERB: &str = "OPTIONS";
 const TRACE_VERB: &str = "TRACE";
 const PATCH_VERB: &str = "PATCH";
 
+const PROPFIND_VERB: &str = "PROPFIND";
+const PROPPATCH_VERB: &str = "PROPPATCH";
+const MKCOL_VERB: &str = "MKCOL";
+const COPY_VERB: &str = "COPY";
+const MOVE_VERB: &str = "MOVE";
+const LOCK_VERB: &str = "LOCK";
+const UNLOCK_VERB: &str = "UNLOCK";
+const REPORT_VERB: &str = "REPORT";
+
+/// regex of up to 8 `tchar`.
+const TCHARS_REGEX: &str = "[!#$%&'*+\\-.^_`|~0-9A-Za-z]{0,8}";
+
+/// delimiters that are not allowed in a `token`.
+const DELIMITERS: &[char] =
+  &['"', '(', ')', ',', '/', ':', ';', '<', '=', '>', '?', '@', '[', '\\', ']', '{', '}', ' '];
+
+/// HTTP request method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Method {
+  Get,
+  Head,
+  Post,
+  Put,
+  Delete,
+  Connect,
+  Options,
+  Trace,
+  Patch,
+  /// any other method `token`, like the WebDAV ones.
+  Extension(String),
+}
+
+impl Method {
+  /// whether the method is safe (RFC 9110 section 9.2.1), meaning it is essentially read-only.
+  ///
+  /// extension methods are safe only if they are registered as such, like `PROPFIND` and `REPORT`.
+  pub fn is_safe(&self) -> bool {
+    match self {
+      Method::Get | Method::Head | Method::Options | Method::Trace => true,
+      Method::Post | Method::Put | Method::Delete | Method::Connect | Method::Patch => false,
+      Method::Extension(method) => matches!(method.as_str(), PROPFIND_VERB | REPORT_VERB),
+    }
+  }
+
+  /// whether the method is idempotent (RFC 9110 section 9.2.2), meaning multiple identical
+  /// requests have the same effect as a single one.
+  pub fn is_idempotent(&self) -> bool {
+    match self {
+      Method::Put | Method::Delete => true,
+      Method::Extension(method) => {
+        matches!(method.as_str(), PROPPATCH_VERB | MKCOL_VERB | COPY_VERB | MOVE_VERB | UNLOCK_VERB)
+          || self.is_safe()
+      }
+      _ => self.is_safe(),
+    }
+  }
+
+  /// whether the request may carry content with defined semantics.
+  ///
+  /// content of `GET`, `HEAD`, `DELETE` and `CONNECT` requests has no defined semantics and
+  /// `TRACE` requests must not carry any, so those methods do not allow a body.
+  pub fn allows_body(&self) -> bool {
+    match self {
+      Method::Post | Method::Put | Method::Patch | Method::Options | Method::Extension(_) => true,
+      Method::Get | Method::Head | Method::Delete | Method::Connect | Method::Trace => false,
+    }
+  }
+}
+
+impl From<&str> for Method {
+  fn from(value: &str) -> Self {
+    match value {
+      GET_VERB => Method::Get,
+      HEAD_VERB => Method::Head,
+      POST_VERB => Method::Post,
+      PUT_VERB => Method::Put,
+      DELETE_VERB => Method::Delete,
+      CONNECT_VERB => Method::Connect,
+      OPTIONS_VERB => Method::Options,
+      TRACE_VERB => Method::Trace,
+      PATCH_VERB => Method::Patch,
+      method => Method::Extension(method.to_string()),
+    }
+  }
+}
+
+impl fmt::Display for Method {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Method::Get => write!(f, "{GET_VERB}"),
+      Method::Head => write!(f, "{HEAD_VERB}"),
+      Method::Post => write!(f, "{POST_VERB}"),
+      Method::Put => write!(f, "{PUT_VERB}"),
+      Method::Delete => write!(f, "{DELETE_VERB}"),
+      Method::Connect => write!(f, "{CONNECT_VERB}"),
+      Method::Options => write!(f, "{OPTIONS_VERB}"),
+      Method::Trace => write!(f, "{TRACE_VERB}"),
+      Method::Patch => write!(f, "{PATCH_VERB}"),
+      Method::Extension(method) => write!(f, "{method}"),
+    }
+  }
+}
+
+/// strategy for generating standard HTTP request method.
+///
+/// # Returns
+/// [`Method`] and its representation.
+pub fn method() -> impl Strategy<Value = (Method, String)> {
+  request_verb().prop_map(|verb| (Method::from(verb.as_str()), verb))
+}
+
+/// strategy for generating standard HTTP request method other than `CONNECT`, which turns the
+/// connection into a tunnel.
+///
+/// # Returns
+/// [`Method`] and its representation.
+pub fn method_without_tunnel() -> impl Strategy<Value = (Method, String)> {
+  prop_oneof![
+    GET_VERB,
+    HEAD_VERB,
+    POST_VERB,
+    PUT_VERB,
+    DELETE_VERB,
+    OPTIONS_VERB,
+    TRACE_VERB,
+    PATCH_VERB,
+  ]
+  .prop_map(|verb| (Method::from(verb.as_str()), verb))
+}
+
+/// strategy for generating standard HTTP request method which allows a body.
+///
+/// see [`Method::allows_body`].
+///
+/// # Returns
+/// `POST`, `PUT`, `PATCH` or `OPTIONS` [`Method`] and its representation.
+pub fn method_with_body() -> impl Strategy<Value = (Method, String)> {
+  prop_oneof![POST_VERB, PUT_VERB, PATCH_VERB, OPTIONS_VERB]
+    .prop_map(|verb| (Method::from(verb.as_str()), verb))
+}
+
+/// strategy for generating extension HTTP request method.
+///
+/// see [`request_verb_extension`].
+///
+/// # Returns
+/// [`Method::Extension`] and its representation.
+pub fn method_extension() -> impl Strategy<Value = (Method, String)> {
+  request_verb_extension().prop_map(|verb| (Method::Extension(verb.clone()), verb))
+}
+
 /// strategy for generating correct HTTP request verb.
 ///
 /// # Returns
@@ -127,11 +286,71 @@ prop_compose! {
   }
 }
 
+/// strategy for generating extension HTTP request verb.
+///
+/// RFC 9110 allows any `token` as a method, this strategy generates either one of the registered
+/// WebDAV methods (`PROPFIND`, `PROPPATCH`, `MKCOL`, `COPY`, `MOVE`, `LOCK`, `UNLOCK`, `REPORT`)
+/// or an arbitrary `token` which is not one of the standard methods.
+pub fn request_verb_extension() -> impl Strategy<Value = String> {
+  prop_oneof![
+    prop_oneof![
+      PROPFIND_VERB,
+      PROPPATCH_VERB,
+      MKCOL_VERB,
+      COPY_VERB,
+      MOVE_VERB,
+      LOCK_VERB,
+      UNLOCK_VERB,
+      REPORT_VERB,
+    ]
+    .prop_map(String::from),
+    token().prop_filter("standard HTTP verb", |verb| {
+      !matches!(
+        verb.as_str(),
+        "GET" | "HEAD" | "POST" | "PUT" | "DELETE" | "CONNECT" | "OPTIONS" | "TRACE" | "PATCH"
+      )
+    }),
+  ]
+}
+
+prop_compose! {
+  /// strategy for generating HTTP request verb which is not a valid `token`.
+  ///
+  /// the verb is made of `tchar` but contains at least one delimiter, space or control character.
+  pub fn request_verb_invalid_token()
+  (
+    prefix in TCHARS_REGEX,
+    invalid in prop_oneof![
+      select(DELIMITERS),
+      (0u8..0x20).prop_map(char::from),
+      Just('\x7f'),
+    ],
+    suffix in TCHARS_REGEX,
+  ) -> String {
+    format!("{prefix}{invalid}{suffix}")
+  }
+}
+
 #[cfg(test)]
 pub(super) mod tests {
   use proptest::prelude::*;
 
   use super::*;
+  use crate::header::tests::is_tchar;
+
+  /// standard methods with whether they are safe, idempotent and allow a body (RFC 9110
+  /// section 9).
+  const STANDARD_METHODS: [(&str, bool, bool, bool); 9] = [
+    ("GET", true, true, false),
+    ("HEAD", true, true, false),
+    ("POST", false, false, true),
+    ("PUT", false, true, true),
+    ("DELETE", false, true, false),
+    ("CONNECT", false, false, false),
+    ("OPTIONS", true, true, true),
+    ("TRACE", true, true, false),
+    ("PATCH", false, false, true),
+  ];
 
   pub(in super::super) fn request_verb_asserts(verb: &str) {
     assert!(matches!(
@@ -145,6 +364,38 @@ pub(super) mod tests {
     fn request_verb_ok(verb in request_verb()) {
       request_verb_asserts(&verb);
     }
+
+    #[test]
+    fn method_without_tunnel_works((method, repr) in method_without_tunnel()) {
+      request_verb_asserts(&repr);
+      assert_ne!(method, Method::Connect);
+    }
+
+    #[test]
+    fn method_with_body_works((method, repr) in method_with_body()) {
+      request_verb_asserts(&repr);
+      assert!(method.allows_body(), "{method} should allow a body");
+    }
+
+    #[test]
+    fn method_works((method, repr) in prop_oneof![method(), method_extension()]) {
+      assert_eq!(method.to_string(), repr);
+      assert_eq!(Method::from(repr.as_str()), method);
+      match STANDARD_METHODS.iter().find(|(name, ..)| *name == repr) {
+        Some((_, safe, idempotent, allows_body)) => {
+          assert_eq!(method.is_safe(), *safe, "unexpected safety of {method}");
+          assert_eq!(method.is_idempotent(), *idempotent, "unexpected idempotency of {method}");
+          assert_eq!(method.allows_body(), *allows_body, "unexpected body allowance of {method}");
+        }
+        None => {
+          assert!(matches!(method, Method::Extension(_)), "{method} should be an extension");
+          assert!(method.allows_body(), "extension method {method} should allow body");
+          if method.is_safe() {
+            assert!(method.is_idempotent(), "safe method {method} should be idempotent");
+          }
+        }
+      }
+    }
   }
 
   proptest! {
@@ -179,5 +430,22 @@ pub(super) mod tests {
         "expected a non-HTTP verb but got {input}"
       );
     }
+
+    #[test]
+    fn request_verb_extension_ok(verb in request_verb_extension()) {
+      assert!(!verb.is_empty() && verb.chars().all(is_tchar), "expected a valid token but got {verb:?}");
+      assert!(
+        !matches!(
+          verb.as_str(),
+          "GET" | "HEAD" | "POST" | "PUT" | "DELETE" | "CONNECT" | "OPTIONS" | "TRACE" | "PATCH"
+        ),
+        "expected an extension verb but got {verb}"
+      );
+    }
+
+    #[test]
+    fn request_verb_invalid_token_ok(verb in request_verb_invalid_token()) {
+      assert!(!verb.chars().all(is_tchar), "expected an invalid token but got {verb:?}");
+    }
   }
 }
@@ -13,21 +13,38 @@ const HTTP_2: &str = "HTTP/2";
 const HTTP_3: &str = "HTTP/3";
 
 /// All valid HTTP versions.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HttpVersion {
+  /// HTTP/0.9, which is never written on the wire as simple requests have no version: it
+  /// displays as an empty string.
+  Http09,
   Http10,
   Http11,
   Http2,
   Http3,
+  /// any other `HTTP/x.y` version, like `HTTP/1.9` or `HTTP/9.9`.
+  Other {
+    major: u8,
+    minor: u8,
+  },
+}
+
+impl HttpVersion {
+  /// whether the version is one of the known HTTP versions.
+  pub fn is_known(&self) -> bool {
+    !matches!(self, HttpVersion::Other { .. })
+  }
 }
 
 impl fmt::Display for HttpVersion {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
+      HttpVersion::Http09 => Ok(()),
       HttpVersion::Http10 => write!(f, "{HTTP_1_0}"),
       HttpVersion::Http11 => write!(f, "{HTTP_1_1}"),
       HttpVersion::Http2 => write!(f, "{HTTP_2}"),
       HttpVersion::Http3 => write!(f, "{HTTP_3}"),
+      HttpVersion::Other { major, minor } => write!(f, "HTTP/{major}.{minor}"),
     }
   }
 }
@@ -45,6 +62,25 @@ pub fn version() -> impl Strategy<Value = (HttpVersion, String)> {
   ]
 }
 
+/// strategy for generating HTTP version with arbitrary `HTTP/<digit>.<digit>` format.
+///
+/// known versions are mapped to their [`HttpVersion`] variant, any other version, like
+/// `HTTP/1.9` or `HTTP/9.9`, is [`HttpVersion::Other`]. `HTTP/0.9` is also
+/// [`HttpVersion::Other`], since HTTP/0.9 requests never carry a version.
+///
+/// # Returns
+/// [`HttpVersion`] and it representation.
+pub fn version_arbitrary() -> impl Strategy<Value = (HttpVersion, String)> {
+  (0u8..=9, 0u8..=9).prop_map(|(major, minor)| {
+    let version = match (major, minor) {
+      (1, 0) => HttpVersion::Http10,
+      (1, 1) => HttpVersion::Http11,
+      (major, minor) => HttpVersion::Other { major, minor },
+    };
+    (version, format!("HTTP/{major}.{minor}"))
+  })
+}
+
 #[cfg(test)]
 pub(super) mod tests {
   use proptest::proptest;
@@ -53,10 +89,14 @@ pub(super) mod tests {
 
   pub(in super::super) fn version_asserts(version: &HttpVersion, repr: &str) {
     match version {
+      HttpVersion::Http09 => assert!(repr.is_empty(), "HTTP/0.9 has no version but got {repr}"),
       HttpVersion::Http10 => assert_eq!(repr, HTTP_1_0, "expected HTTP version 1.0 but got {repr}"),
       HttpVersion::Http11 => assert_eq!(repr, HTTP_1_1, "expected HTTP version 1.1 but got {repr}"),
       HttpVersion::Http2 => assert_eq!(repr, HTTP_2, "expected HTTP version 2 but got {repr}"),
       HttpVersion::Http3 => assert_eq!(repr, HTTP_3, "expected HTTP version 3 but got {repr}"),
+      HttpVersion::Other { major, minor } => {
+        assert_eq!(repr, format!("HTTP/{major}.{minor}"), "unexpected HTTP version {repr}");
+      }
     }
   }
 
@@ -65,5 +105,13 @@ pub(super) mod tests {
     fn version_works((version, repr) in version()) {
       version_asserts(&version, &repr);
     }
+
+    #[test]
+    fn version_arbitrary_works((version, repr) in version_arbitrary()) {
+      version_asserts(&version, &repr);
+      assert_ne!(version, HttpVersion::Http09, "HTTP/0.9 is never written on the wire");
+      let known = matches!(repr.as_str(), "HTTP/1.0" | "HTTP/1.1");
+      assert_eq!(version.is_known(), known, "unexpected known status for {repr}");
+    }
   }
 }